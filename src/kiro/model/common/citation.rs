@@ -0,0 +1,328 @@
+//! 引用格式转换
+//!
+//! 将代码引用转换为其他 API 使用的引用/注解格式
+
+use serde_json::{Value, json};
+
+use super::reference::Reference;
+
+impl Reference {
+    /// 引用的展示标题（优先使用仓库名，其次为附加信息）
+    fn display_title(&self) -> Option<&str> {
+        self.repository.as_deref().or(self.information.as_deref())
+    }
+
+    /// 转换为 OpenAI 风格的 `url_citation` 注解
+    ///
+    /// 内容范围映射为 `start_index` / `end_index`，未设置范围时省略这两个字段
+    pub fn to_openai_annotation(&self) -> Value {
+        let mut citation = json!({
+            "url": self.url,
+            "title": self.display_title(),
+        });
+        if let Some(span) = self.recommendation_content_span {
+            citation["start_index"] = json!(span.start);
+            citation["end_index"] = json!(span.end);
+        }
+        json!({
+            "type": "url_citation",
+            "url_citation": citation,
+        })
+    }
+
+    /// 转换为 Anthropic 风格的 `char_location` 引用
+    ///
+    /// `cited_text` 从 `source_text` 中按内容范围截取，字节偏移转换为字符索引；
+    /// 范围无效时 `cited_text` 为空，字符索引为 `null`
+    pub fn to_anthropic_citation(&self, source_text: &str) -> Value {
+        let located = self.recommendation_content_span.and_then(|span| {
+            let cited = span.slice(source_text)?;
+            let start = source_text[..span.start as usize].chars().count();
+            Some((cited, start, start + cited.chars().count()))
+        });
+        let (cited_text, start, end) = match located {
+            Some((cited, start, end)) => (cited, Some(start), Some(end)),
+            None => ("", None, None),
+        };
+        json!({
+            "type": "char_location",
+            "cited_text": cited_text,
+            "document_title": self.display_title(),
+            "start_char_index": start,
+            "end_char_index": end,
+        })
+    }
+
+    /// 转换为终端可点击的 OSC 8 超链接
+    ///
+    /// 标签优先使用仓库名，其次为附加信息，都没有时使用链接本身；
+    /// 会移除控制字符以防止注入其他转义序列。没有链接时返回 `None`
+    pub fn to_osc8(&self) -> Option<String> {
+        let url = strip_control(self.url.as_deref()?);
+        let label = strip_control(self.display_title().unwrap_or(&url));
+        Some(format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, label))
+    }
+
+    /// 转换为 SARIF `location` 对象
+    ///
+    /// 内容范围通过 `source_text` 映射为 `region`（行列均从 1 开始）。
+    /// 列号和 `charOffset` / `charLength` 按 SARIF 2.1.0 默认的
+    /// `utf16CodeUnits` 计算；范围无效时省略 `region`，仅保留 `artifactLocation`
+    pub fn to_sarif_location(&self, uri: &str, source_text: &str) -> Value {
+        let mut physical_location = json!({
+            "artifactLocation": { "uri": uri },
+        });
+        if let Some(span) = self.recommendation_content_span
+            && let Some(utf16) = span.to_utf16(source_text)
+            && let Some(((start_line, start_col), (end_line, end_col))) =
+                span.to_line_col(source_text)
+        {
+            let start_col = utf16_column(source_text, span.start as usize, start_col);
+            let end_col = utf16_column(source_text, span.end as usize, end_col);
+            physical_location["region"] = json!({
+                "startLine": start_line,
+                "startColumn": start_col + 1,
+                "endLine": end_line,
+                "endColumn": end_col + 1,
+                "charOffset": utf16.start,
+                "charLength": utf16.len(),
+            });
+        }
+        json!({ "physicalLocation": physical_location })
+    }
+
+    /// 转换为 BibTeX `@misc` 条目
+    ///
+    /// 条目键由内容 ID 生成；仓库名作为 `title`，许可证写入 `note`，
+    /// 缺失的字段直接省略
+    pub fn to_bibtex(&self) -> String {
+        let mut fields = Vec::new();
+        if let Some(repository) = self.repository.as_deref() {
+            fields.push(format!("  title = {{{}}}", escape_bibtex(repository)));
+        }
+        if let Some(url) = self.url.as_deref() {
+            fields.push(format!("  url = {{{}}}", escape_bibtex(url)));
+        }
+        if let Some(license) = self.license_name.as_deref() {
+            fields.push(format!("  note = {{License: {}}}", escape_bibtex(license)));
+        }
+        format!(
+            "@misc{{ref{:016x},\n{}\n}}",
+            self.content_id(),
+            fields.join(",\n")
+        )
+    }
+}
+
+/// 转义 BibTeX 字段值中的花括号和反斜杠
+///
+/// BibTeX 会把 `\{` 仍计为花括号，因此改用 LaTeX 文本命令替换，保证字段内花括号平衡
+fn escape_bibtex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '{' => escaped.push_str(r"{\textbraceleft}"),
+            '}' => escaped.push_str(r"{\textbraceright}"),
+            '\\' => escaped.push_str(r"{\textbackslash}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 将行内字节列号转换为 UTF-16 码元列号
+///
+/// `offset` 为字节偏移，`byte_col` 为其在所在行内的字节列号，二者须位于字符边界
+fn utf16_column(text: &str, offset: usize, byte_col: u32) -> usize {
+    text[offset - byte_col as usize..offset]
+        .encode_utf16()
+        .count()
+}
+
+/// 移除字符串中的控制字符
+fn strip_control(value: &str) -> String {
+    value.chars().filter(|c| !c.is_control()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kiro::model::common::span::ContentSpan;
+
+    #[test]
+    fn test_to_openai_annotation() {
+        let reference = Reference::new()
+            .with_url("https://github.com/owner/repo")
+            .with_repository("owner/repo")
+            .with_span(ContentSpan::new(3, 9));
+
+        assert_eq!(
+            reference.to_openai_annotation(),
+            json!({
+                "type": "url_citation",
+                "url_citation": {
+                    "url": "https://github.com/owner/repo",
+                    "title": "owner/repo",
+                    "start_index": 3,
+                    "end_index": 9,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_openai_annotation_without_span() {
+        let reference = Reference::new().with_url("https://example.com");
+        let annotation = reference.to_openai_annotation();
+
+        assert_eq!(annotation["url_citation"]["url"], "https://example.com");
+        assert!(annotation["url_citation"].get("start_index").is_none());
+    }
+
+    #[test]
+    fn test_to_anthropic_citation() {
+        let source = "use serde::Serialize;\nfn main() {}";
+        let reference = Reference::new()
+            .with_repository("serde-rs/serde")
+            .with_span(ContentSpan::new(4, 9));
+
+        assert_eq!(
+            reference.to_anthropic_citation(source),
+            json!({
+                "type": "char_location",
+                "cited_text": "serde",
+                "document_title": "serde-rs/serde",
+                "start_char_index": 4,
+                "end_char_index": 9,
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_anthropic_citation_uses_char_indices() {
+        // "你好" 占 6 字节、2 个字符
+        let source = "你好 world";
+        let reference = Reference::new()
+            .with_information("greeting")
+            .with_span(ContentSpan::new(7, 12));
+        let citation = reference.to_anthropic_citation(source);
+
+        assert_eq!(citation["cited_text"], "world");
+        assert_eq!(citation["start_char_index"], 3);
+        assert_eq!(citation["end_char_index"], 8);
+    }
+
+    #[test]
+    fn test_to_sarif_location() {
+        let source = "fn main() {\n    helper();\n}\n";
+        let reference = Reference::new()
+            .with_url("https://github.com/owner/repo")
+            .with_span(ContentSpan::new(16, 22));
+
+        assert_eq!(
+            reference.to_sarif_location("src/main.rs", source),
+            json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "src/main.rs" },
+                    "region": {
+                        "startLine": 2,
+                        "startColumn": 5,
+                        "endLine": 2,
+                        "endColumn": 11,
+                        "charOffset": 16,
+                        "charLength": 6,
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_sarif_location_uses_utf16_columns() {
+        // "你" "好" 各占 3 字节、1 个 UTF-16 码元；"😀" 占 4 字节、2 个码元
+        let source = "// 你好\nlet s = \"😀ok\";\n";
+        let reference = Reference::new().with_span(ContentSpan::new(19, 25));
+        let region = &reference.to_sarif_location("a.rs", source)["physicalLocation"]["region"];
+
+        assert_eq!(region["startLine"], 2);
+        assert_eq!(region["startColumn"], 10);
+        assert_eq!(region["endColumn"], 14);
+        assert_eq!(region["charOffset"], 15);
+        assert_eq!(region["charLength"], 4);
+    }
+
+    #[test]
+    fn test_to_sarif_location_without_span() {
+        let location = Reference::new().to_sarif_location("a.rs", "");
+        assert!(location["physicalLocation"].get("region").is_none());
+    }
+
+    #[test]
+    fn test_to_osc8() {
+        let reference = Reference::new()
+            .with_url("https://github.com/owner/repo")
+            .with_repository("owner/repo");
+
+        assert_eq!(
+            reference.to_osc8().as_deref(),
+            Some("\x1b]8;;https://github.com/owner/repo\x1b\\owner/repo\x1b]8;;\x1b\\")
+        );
+    }
+
+    #[test]
+    fn test_to_osc8_strips_control_characters() {
+        let reference = Reference::new()
+            .with_url("https://example.com")
+            .with_information("evil\x1b]8;;label");
+        let link = reference.to_osc8().unwrap();
+
+        assert!(link.contains("https://example.com"));
+        assert!(link.contains("evil]8;;label"));
+        assert_eq!(Reference::new().with_repository("o/r").to_osc8(), None);
+    }
+
+    #[test]
+    fn test_to_bibtex() {
+        let reference = Reference::new()
+            .with_url("https://github.com/owner/repo")
+            .with_repository("owner/repo")
+            .with_license_name("MIT");
+        let entry = reference.to_bibtex();
+
+        assert!(entry.starts_with(&format!("@misc{{ref{:016x},", reference.content_id())));
+        assert!(entry.contains("title = {owner/repo}"));
+        assert!(entry.contains("url = {https://github.com/owner/repo}"));
+        assert!(entry.contains("note = {License: MIT}"));
+        assert!(entry.ends_with('}'));
+    }
+
+    #[test]
+    fn test_to_bibtex_escapes_braces() {
+        let reference = Reference::new()
+            .with_url("https://example.com")
+            .with_repository(r"evil}, author = {x\y");
+        let entry = reference.to_bibtex();
+        let title = entry
+            .lines()
+            .find(|line| line.trim_start().starts_with("title"))
+            .unwrap()
+            .trim_end_matches(',');
+
+        assert_eq!(
+            title,
+            r"  title = {evil{\textbraceright}, author = {\textbraceleft}x{\textbackslash}y}"
+        );
+        // 字段值内的花括号保持平衡，无法提前闭合字段
+        let value = &title["  title = ".len()..];
+        let mut depth = 0;
+        for (index, c) in value.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            assert!(depth > 0 || index == value.len() - 1);
+        }
+        assert_eq!(depth, 0);
+    }
+}
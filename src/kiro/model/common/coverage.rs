@@ -0,0 +1,179 @@
+//! 引用范围统计
+//!
+//! 基于多个引用的内容范围进行汇总计算
+
+use super::reference::Reference;
+use super::span::ContentSpan;
+
+/// 遍历引用中已设置的内容范围
+fn spans(refs: &[Reference]) -> impl Iterator<Item = ContentSpan> + '_ {
+    refs.iter()
+        .filter_map(|reference| reference.recommendation_content_span)
+}
+
+/// 计算覆盖所有引用范围的最小范围
+///
+/// 忽略未设置范围的引用；没有任何范围时返回 `None`
+#[allow(dead_code)]
+pub fn covering_span(refs: &[Reference]) -> Option<ContentSpan> {
+    spans(refs)
+        .reduce(|acc, span| ContentSpan::new(acc.start.min(span.start), acc.end.max(span.end)))
+}
+
+/// 合并引用范围
+///
+/// 忽略空范围和倒置范围，按起始位置排序后合并重叠或相邻的范围
+pub fn merged_spans(refs: &[Reference]) -> Vec<ContentSpan> {
+    let mut sorted: Vec<ContentSpan> = spans(refs).filter(|span| !span.is_empty()).collect();
+    sorted.sort_by_key(|span| (span.start, span.end));
+
+    let mut merged: Vec<ContentSpan> = Vec::with_capacity(sorted.len());
+    for span in sorted {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// 计算引用覆盖的文本比例
+///
+/// 合并重叠范围后，统计落在 `[0, total_len)` 内的长度并除以 `total_len`，
+/// 结果限制在 `[0, 1]`；`total_len` 不为正时返回 0
+#[allow(dead_code)]
+pub fn coverage_ratio(refs: &[Reference], total_len: i32) -> f64 {
+    if total_len <= 0 {
+        return 0.0;
+    }
+    let covered: i64 = merged_spans(refs)
+        .iter()
+        .map(|span| i64::from((span.end.min(total_len) - span.start.max(0)).max(0)))
+        .sum();
+    (covered as f64 / f64::from(total_len)).clamp(0.0, 1.0)
+}
+
+/// 计算未被任何引用覆盖的范围
+///
+/// 返回合并后的引用范围在 `[0, total_len)` 内的补集，按位置排序；
+/// `total_len` 不为正时返回空列表
+#[allow(dead_code)]
+pub fn uncovered_spans(refs: &[Reference], total_len: i32) -> Vec<ContentSpan> {
+    if total_len <= 0 {
+        return Vec::new();
+    }
+    let mut gaps = Vec::new();
+    let mut cursor = 0;
+    for span in merged_spans(refs) {
+        let start = span.start.clamp(0, total_len);
+        if start > cursor {
+            gaps.push(ContentSpan::new(cursor, start));
+        }
+        cursor = cursor.max(span.end.min(total_len));
+    }
+    if cursor < total_len {
+        gaps.push(ContentSpan::new(cursor, total_len));
+    }
+    gaps
+}
+
+/// 获取范围包含指定位置的引用
+#[allow(dead_code)]
+pub fn references_at_offset(refs: &[Reference], offset: i32) -> Vec<&Reference> {
+    refs.iter()
+        .filter(|reference| {
+            reference
+                .recommendation_content_span
+                .is_some_and(|span| span.contains(offset))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_covering_span() {
+        let refs = vec![
+            Reference::new()
+                .with_url("https://a")
+                .with_span_range(10..20),
+            Reference::new().with_url("https://b"),
+            Reference::new().with_url("https://c").with_span_range(3..8),
+            Reference::new()
+                .with_url("https://d")
+                .with_span_range(15..30),
+        ];
+        assert_eq!(covering_span(&refs), Some(ContentSpan::new(3, 30)));
+    }
+
+    #[test]
+    fn test_covering_span_without_spans() {
+        assert_eq!(covering_span(&[Reference::new()]), None);
+    }
+
+    #[test]
+    fn test_merged_spans() {
+        let refs = vec![
+            Reference::new().with_url("a").with_span_range(10..20),
+            Reference::new().with_url("b").with_span_range(0..5),
+            Reference::new().with_url("c").with_span_range(15..25),
+            Reference::new().with_url("d").with_span_range(25..30),
+            Reference::new().with_url("e").with_span_range(40..40),
+        ];
+        assert_eq!(
+            merged_spans(&refs),
+            vec![ContentSpan::new(0, 5), ContentSpan::new(10, 30)]
+        );
+    }
+
+    #[test]
+    fn test_coverage_ratio() {
+        let refs = vec![
+            Reference::new().with_url("a").with_span_range(0..30),
+            Reference::new().with_url("b").with_span_range(20..50),
+            Reference::new().with_url("c"),
+        ];
+        assert!((coverage_ratio(&refs, 100) - 0.5).abs() < f64::EPSILON);
+        // 超出总长度的部分不计入
+        assert!((coverage_ratio(&refs, 40) - 1.0).abs() < f64::EPSILON);
+        assert_eq!(coverage_ratio(&refs, 0), 0.0);
+    }
+
+    #[test]
+    fn test_references_at_offset() {
+        let refs = vec![
+            Reference::new().with_url("a").with_span_range(0..10),
+            Reference::new().with_url("b").with_span_range(5..15),
+            Reference::new().with_url("c").with_span_range(12..20),
+            Reference::new().with_url("d"),
+        ];
+
+        let hits: Vec<&str> = references_at_offset(&refs, 7)
+            .iter()
+            .filter_map(|reference| reference.url())
+            .collect();
+        assert_eq!(hits, vec!["a", "b"]);
+        assert!(references_at_offset(&refs, 25).is_empty());
+    }
+
+    #[test]
+    fn test_uncovered_spans() {
+        let refs = vec![
+            Reference::new().with_span_range(10..20),
+            Reference::new().with_span_range(30..40),
+        ];
+
+        assert_eq!(
+            uncovered_spans(&refs, 50),
+            vec![
+                ContentSpan::new(0, 10),
+                ContentSpan::new(20, 30),
+                ContentSpan::new(40, 50),
+            ]
+        );
+        assert_eq!(uncovered_spans(&[], 5), vec![ContentSpan::new(0, 5)]);
+        assert!(uncovered_spans(&refs, 0).is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+//! 定制化配置定义
+
+use serde::{Deserialize, Serialize};
+
+/// 定制化配置
+///
+/// 通过 ARN 标识的模型定制化
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Customization {
+    /// 定制化 ARN
+    pub arn: String,
+    /// 定制化名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// 定制化描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[allow(dead_code)]
+impl Customization {
+    /// 创建新的定制化配置
+    pub fn new(arn: impl Into<String>) -> Self {
+        Self {
+            arn: arn.into(),
+            name: None,
+            description: None,
+        }
+    }
+
+    /// 设置名称
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// 设置描述
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// 解析 ARN 中的区域段
+    ///
+    /// ARN 格式为 `arn:partition:service:region:account:resource`；
+    /// 格式不符或区域段为空时返回 `None`
+    pub fn region(&self) -> Option<&str> {
+        let mut segments = self.arn.splitn(6, ':');
+        if segments.next()? != "arn" {
+            return None;
+        }
+        let region = segments.nth(2)?;
+        // 确保 ARN 至少包含 account 和 resource 段
+        segments.nth(1)?;
+        Some(region).filter(|region| !region.is_empty())
+    }
+
+    /// 判断 ARN 区域是否与指定区域一致（不区分大小写）
+    pub fn matches_region(&self, region: &str) -> bool {
+        self.region()
+            .is_some_and(|own| own.eq_ignore_ascii_case(region))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARN: &str = "arn:aws:codewhisperer:us-east-1:123456789012:customization/ABCDEF";
+
+    #[test]
+    fn test_region() {
+        assert_eq!(Customization::new(ARN).region(), Some("us-east-1"));
+        assert_eq!(Customization::new("not-an-arn").region(), None);
+        assert_eq!(
+            Customization::new("arn:aws:codewhisperer::123:customization/x").region(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_matches_region() {
+        let customization = Customization::new(ARN);
+
+        assert!(customization.matches_region("us-east-1"));
+        assert!(!customization.matches_region("eu-central-1"));
+    }
+}
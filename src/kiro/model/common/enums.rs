@@ -0,0 +1,187 @@
+//! 共享枚举定义
+
+use serde::{Deserialize, Serialize};
+
+/// 用户意图
+///
+/// 描述后续提示（followup prompt）建议的操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UserIntent {
+    /// 解释选中的代码
+    ExplainCodeSelection,
+    /// 建议替代实现
+    SuggestAlternateImplementation,
+    /// 应用通用最佳实践
+    ApplyCommonBestPractices,
+    /// 改进代码
+    ImproveCode,
+    /// 展示示例
+    ShowExamples,
+    /// 引用来源
+    CiteSources,
+    /// 逐行解释
+    ExplainLineByLine,
+    /// 生成 CloudFormation 模板
+    GenerateCloudformationTemplate,
+    /// 生成单元测试
+    GenerateUnitTests,
+    /// 代码生成
+    CodeGeneration,
+}
+
+/// 所有用户意图的线上字符串值，顺序与 [`UserIntent::ALL`] 一致
+#[allow(dead_code)]
+pub const USER_INTENT_WIRE_VALUES: &[&str] = &[
+    "EXPLAIN_CODE_SELECTION",
+    "SUGGEST_ALTERNATE_IMPLEMENTATION",
+    "APPLY_COMMON_BEST_PRACTICES",
+    "IMPROVE_CODE",
+    "SHOW_EXAMPLES",
+    "CITE_SOURCES",
+    "EXPLAIN_LINE_BY_LINE",
+    "GENERATE_CLOUDFORMATION_TEMPLATE",
+    "GENERATE_UNIT_TESTS",
+    "CODE_GENERATION",
+];
+
+#[allow(dead_code)]
+impl UserIntent {
+    /// 所有用户意图
+    pub const ALL: [UserIntent; 10] = [
+        Self::ExplainCodeSelection,
+        Self::SuggestAlternateImplementation,
+        Self::ApplyCommonBestPractices,
+        Self::ImproveCode,
+        Self::ShowExamples,
+        Self::CiteSources,
+        Self::ExplainLineByLine,
+        Self::GenerateCloudformationTemplate,
+        Self::GenerateUnitTests,
+        Self::CodeGeneration,
+    ];
+
+    /// 获取意图优先级（数值越小越优先）
+    ///
+    /// 直接产出代码的意图排在前面，解释类意图排在后面
+    pub fn priority(&self) -> u8 {
+        match self {
+            Self::CodeGeneration => 0,
+            Self::GenerateUnitTests => 1,
+            Self::ImproveCode => 2,
+            Self::ApplyCommonBestPractices => 3,
+            Self::SuggestAlternateImplementation => 4,
+            Self::GenerateCloudformationTemplate => 5,
+            Self::ExplainCodeSelection => 6,
+            Self::ExplainLineByLine => 7,
+            Self::ShowExamples => 8,
+            Self::CiteSources => 9,
+        }
+    }
+
+    /// 获取旧版客户端使用的整数编码
+    ///
+    /// 编码与 [`Self::ALL`] 的顺序一致，属于线上协议，不得调整
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::ExplainCodeSelection => 0,
+            Self::SuggestAlternateImplementation => 1,
+            Self::ApplyCommonBestPractices => 2,
+            Self::ImproveCode => 3,
+            Self::ShowExamples => 4,
+            Self::CiteSources => 5,
+            Self::ExplainLineByLine => 6,
+            Self::GenerateCloudformationTemplate => 7,
+            Self::GenerateUnitTests => 8,
+            Self::CodeGeneration => 9,
+        }
+    }
+
+    /// 从整数编码解析，未知编码返回 `None`
+    pub fn from_code(code: u64) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|intent| u64::from(intent.code()) == code)
+    }
+
+    /// 判断该意图是否需要用户先选中代码区域
+    ///
+    /// 解释、改进、生成测试等针对已有代码的意图需要选区；
+    /// 代码生成、示例、引用来源等意图不需要
+    pub fn requires_selection(&self) -> bool {
+        matches!(
+            self,
+            Self::ExplainCodeSelection
+                | Self::ExplainLineByLine
+                | Self::ImproveCode
+                | Self::ApplyCommonBestPractices
+                | Self::SuggestAlternateImplementation
+                | Self::GenerateUnitTests
+        )
+    }
+
+    /// 获取该意图默认的后续提示文本
+    ///
+    /// 用于自动生成后续提示，见 [`FollowupPrompt::for_intent`]
+    ///
+    /// [`FollowupPrompt::for_intent`]: super::followup::FollowupPrompt::for_intent
+    pub fn default_followup_content(&self) -> &'static str {
+        match self {
+            Self::ExplainCodeSelection => "Explain the selected code",
+            Self::SuggestAlternateImplementation => "Suggest an alternate implementation",
+            Self::ApplyCommonBestPractices => "Apply common best practices",
+            Self::ImproveCode => "Improve this code",
+            Self::ShowExamples => "Show me some examples",
+            Self::CiteSources => "Cite your sources",
+            Self::ExplainLineByLine => "Explain this code line by line",
+            Self::GenerateCloudformationTemplate => "Generate a CloudFormation template",
+            Self::GenerateUnitTests => "Generate unit tests",
+            Self::CodeGeneration => "Generate the code",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wire_values_round_trip() {
+        assert_eq!(USER_INTENT_WIRE_VALUES.len(), UserIntent::ALL.len());
+        for (wire, intent) in USER_INTENT_WIRE_VALUES.iter().zip(UserIntent::ALL) {
+            let parsed: UserIntent = serde_json::from_value(serde_json::json!(wire)).unwrap();
+            assert_eq!(parsed, intent);
+            assert_eq!(serde_json::to_value(intent).unwrap(), *wire);
+        }
+    }
+
+    #[test]
+    fn test_requires_selection() {
+        assert!(UserIntent::ExplainCodeSelection.requires_selection());
+        assert!(UserIntent::ImproveCode.requires_selection());
+        assert!(!UserIntent::CodeGeneration.requires_selection());
+        assert!(!UserIntent::ShowExamples.requires_selection());
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        for intent in UserIntent::ALL {
+            assert_eq!(
+                UserIntent::from_code(u64::from(intent.code())),
+                Some(intent)
+            );
+        }
+        assert_eq!(UserIntent::from_code(10), None);
+    }
+
+    #[test]
+    fn test_default_followup_content() {
+        let contents: std::collections::HashSet<&str> = UserIntent::ALL
+            .iter()
+            .map(|intent| intent.default_followup_content())
+            .collect();
+
+        assert_eq!(contents.len(), UserIntent::ALL.len());
+        assert!(contents.iter().all(|content| !content.is_empty()));
+    }
+}
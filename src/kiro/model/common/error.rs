@@ -0,0 +1,85 @@
+//! 共享类型错误定义
+
+use std::fmt;
+
+/// 模型校验错误类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelError {
+    /// 引用设置了内容范围，但缺少来源信息（url / repository / information）
+    SpanWithoutSource,
+    /// 必填字段为空
+    EmptyField(&'static str),
+    /// 无效的 URL
+    InvalidUrl(String),
+    /// 序列化失败
+    Serialize(String),
+    /// 内容范围长度为负
+    NegativeSpanLength(i32),
+    /// 内容范围结束位置溢出
+    SpanOverflow { start: i32, len: i32 },
+    /// 内容范围缺少 end 或 length
+    MissingSpanEnd,
+    /// SPDX 许可证表达式语法错误或包含未知标识
+    InvalidLicenseExpression(String),
+    /// 内容范围无效或超出上限
+    SpanOutOfBounds { start: i32, end: i32, limit: i32 },
+}
+
+impl std::error::Error for ModelError {}
+
+impl fmt::Display for ModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SpanWithoutSource => {
+                write!(f, "引用设置了内容范围, 但缺少 url/repository/information")
+            }
+            Self::EmptyField(field) => write!(f, "必填字段为空: {}", field),
+            Self::InvalidUrl(url) => write!(f, "无效的 URL: {}", url),
+            Self::Serialize(msg) => write!(f, "序列化失败: {}", msg),
+            Self::NegativeSpanLength(len) => write!(f, "内容范围长度为负: {}", len),
+            Self::SpanOverflow { start, len } => {
+                write!(f, "内容范围结束位置溢出: start={}, len={}", start, len)
+            }
+            Self::MissingSpanEnd => write!(f, "内容范围缺少 end 或 length"),
+            Self::InvalidLicenseExpression(expression) => {
+                write!(f, "无效的 SPDX 许可证表达式: {}", expression)
+            }
+            Self::SpanOutOfBounds { start, end, limit } => {
+                write!(
+                    f,
+                    "内容范围无效或超出上限: {}..{} (上限 {})",
+                    start, end, limit
+                )
+            }
+        }
+    }
+}
+
+/// 共享类型反序列化错误
+///
+/// 在 serde 错误之外附带出错字段的路径和附近的输入片段，便于定位大响应中的问题
+#[derive(Debug)]
+pub struct ParseError {
+    /// 出错字段路径（如 `recommendationContentSpan.start`）
+    pub path: String,
+    /// 出错位置附近的输入片段
+    pub snippet: String,
+    /// 原始 serde 错误
+    pub source: serde_json::Error,
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "反序列化失败 (字段 {}): {}, 附近输入: {}",
+            self.path, self.source, self.snippet
+        )
+    }
+}
@@ -0,0 +1,200 @@
+//! 后续提示定义
+//!
+//! 定义响应中附带的后续提示及其辅助函数
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::enums::UserIntent;
+
+/// 后续提示
+///
+/// 助手建议用户接下来可以发送的提示
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowupPrompt {
+    /// 提示内容
+    pub content: String,
+    /// 用户意图
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_intent: Option<UserIntent>,
+}
+
+#[allow(dead_code)]
+impl FollowupPrompt {
+    /// 创建新的后续提示
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            user_intent: None,
+        }
+    }
+
+    /// 按意图创建后续提示，内容使用 [`UserIntent::default_followup_content`]
+    pub fn for_intent(intent: UserIntent) -> Self {
+        Self::new(intent.default_followup_content()).with_user_intent(intent)
+    }
+
+    /// 设置用户意图
+    pub fn with_user_intent(mut self, intent: UserIntent) -> Self {
+        self.user_intent = Some(intent);
+        self
+    }
+
+    /// 获取优先级（无意图的排在最后）
+    pub fn priority(&self) -> u8 {
+        self.user_intent.map_or(u8::MAX, |intent| intent.priority())
+    }
+
+    /// 获取稳定排序键：先按意图优先级，再按内容
+    pub fn sort_key(&self) -> (u8, &str) {
+        (self.priority(), &self.content)
+    }
+}
+
+/// 按意图优先级排序后续提示
+///
+/// 排序是稳定的，相同优先级的提示保持原有顺序；无意图的提示排在最后
+#[allow(dead_code)]
+pub fn sort_followups_by_priority(prompts: &mut [FollowupPrompt]) {
+    prompts.sort_by_key(FollowupPrompt::priority);
+}
+
+/// 收集后续提示中出现的不同用户意图
+///
+/// 按首次出现顺序去重，忽略没有意图的提示
+#[allow(dead_code)]
+pub fn followup_intents(prompts: &[FollowupPrompt]) -> Vec<UserIntent> {
+    let mut intents = Vec::new();
+    for intent in prompts.iter().filter_map(|prompt| prompt.user_intent) {
+        if !intents.contains(&intent) {
+            intents.push(intent);
+        }
+    }
+    intents
+}
+
+/// 统计后续提示中各用户意图出现的次数
+///
+/// 未设置意图的提示不计入
+#[allow(dead_code)]
+pub fn intent_histogram(prompts: &[FollowupPrompt]) -> HashMap<UserIntent, usize> {
+    let mut histogram = HashMap::new();
+    for intent in prompts.iter().filter_map(|prompt| prompt.user_intent) {
+        *histogram.entry(intent).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// 将后续提示渲染为编号菜单
+///
+/// 每行格式为 `序号) 内容`，序号从 1 开始，行之间以换行分隔
+#[allow(dead_code)]
+pub fn render_followup_menu(prompts: &[FollowupPrompt]) -> String {
+    prompts
+        .iter()
+        .enumerate()
+        .map(|(index, prompt)| format!("{}) {}", index + 1, prompt.content))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_followups_by_priority() {
+        let mut prompts = vec![
+            FollowupPrompt::new("no intent"),
+            FollowupPrompt::new("explain").with_user_intent(UserIntent::ExplainCodeSelection),
+            FollowupPrompt::new("generate").with_user_intent(UserIntent::CodeGeneration),
+            FollowupPrompt::new("cite").with_user_intent(UserIntent::CiteSources),
+            FollowupPrompt::new("improve").with_user_intent(UserIntent::ImproveCode),
+        ];
+
+        sort_followups_by_priority(&mut prompts);
+
+        let order: Vec<&str> = prompts.iter().map(|p| p.content.as_str()).collect();
+        assert_eq!(
+            order,
+            vec!["generate", "improve", "explain", "cite", "no intent"]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_followup() {
+        let json = r#"{"content":"Explain more","userIntent":"EXPLAIN_CODE_SELECTION"}"#;
+        let prompt: FollowupPrompt = serde_json::from_str(json).unwrap();
+        assert_eq!(prompt.user_intent, Some(UserIntent::ExplainCodeSelection));
+    }
+
+    #[test]
+    fn test_render_followup_menu() {
+        let prompts = vec![
+            FollowupPrompt::new("Explain this code"),
+            FollowupPrompt::new("Write tests").with_user_intent(UserIntent::GenerateUnitTests),
+        ];
+        assert_eq!(
+            render_followup_menu(&prompts),
+            "1) Explain this code\n2) Write tests"
+        );
+        assert_eq!(render_followup_menu(&[]), "");
+    }
+
+    #[test]
+    fn test_followup_intents() {
+        let prompts = vec![
+            FollowupPrompt::new("a").with_user_intent(UserIntent::ShowExamples),
+            FollowupPrompt::new("b"),
+            FollowupPrompt::new("c").with_user_intent(UserIntent::ImproveCode),
+            FollowupPrompt::new("d").with_user_intent(UserIntent::ShowExamples),
+        ];
+        assert_eq!(
+            followup_intents(&prompts),
+            vec![UserIntent::ShowExamples, UserIntent::ImproveCode]
+        );
+    }
+
+    #[test]
+    fn test_for_intent() {
+        let prompt = FollowupPrompt::for_intent(UserIntent::GenerateUnitTests);
+
+        assert_eq!(prompt.content, "Generate unit tests");
+        assert_eq!(prompt.user_intent, Some(UserIntent::GenerateUnitTests));
+    }
+
+    #[test]
+    fn test_sort_key() {
+        let mut prompts = [
+            FollowupPrompt::new("b explain").with_user_intent(UserIntent::ExplainCodeSelection),
+            FollowupPrompt::new("z plain"),
+            FollowupPrompt::new("b tests").with_user_intent(UserIntent::GenerateUnitTests),
+            FollowupPrompt::new("a explain").with_user_intent(UserIntent::ExplainCodeSelection),
+            FollowupPrompt::new("a tests").with_user_intent(UserIntent::GenerateUnitTests),
+        ];
+        prompts.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+        let contents: Vec<&str> = prompts.iter().map(|p| p.content.as_str()).collect();
+
+        assert_eq!(
+            contents,
+            vec!["a tests", "b tests", "a explain", "b explain", "z plain"]
+        );
+    }
+
+    #[test]
+    fn test_intent_histogram() {
+        let prompts = [
+            FollowupPrompt::for_intent(UserIntent::ImproveCode),
+            FollowupPrompt::new("plain"),
+            FollowupPrompt::for_intent(UserIntent::ShowExamples),
+            FollowupPrompt::new("again").with_user_intent(UserIntent::ImproveCode),
+        ];
+        let histogram = intent_histogram(&prompts);
+
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram[&UserIntent::ImproveCode], 2);
+        assert_eq!(histogram[&UserIntent::ShowExamples], 1);
+    }
+}
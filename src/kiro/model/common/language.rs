@@ -0,0 +1,500 @@
+//! 编程语言定义
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+/// 语言名称
+///
+/// 常量语言和驻留的名称借用静态字符串，其余名称持有自身的 `String`
+pub type LanguageName = Cow<'static, str>;
+
+/// 语言名称驻留器
+///
+/// 高吞吐解析时大量重复的语言名称可通过驻留共享同一份存储，
+/// 名称随驻留器一起释放
+#[derive(Debug, Default)]
+pub struct LanguageInterner {
+    names: Mutex<HashSet<Arc<str>>>,
+}
+
+impl LanguageInterner {
+    /// 创建新的驻留器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 驻留语言名称，相同名称返回同一个 `Arc`
+    pub fn intern(&self, name: &str) -> Arc<str> {
+        let mut names = self.names.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(existing) = names.get(name) {
+            return Arc::clone(existing);
+        }
+        let interned: Arc<str> = Arc::from(name);
+        names.insert(Arc::clone(&interned));
+        interned
+    }
+
+    /// 已驻留的名称数量
+    pub fn len(&self) -> usize {
+        self.names.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// 判断是否尚未驻留任何名称
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 编程语言
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgrammingLanguage {
+    /// 语言名称（如 "rust"、"python"）
+    pub language_name: LanguageName,
+}
+
+impl ProgrammingLanguage {
+    pub const C: Self = Self::from_static("c");
+    pub const CPP: Self = Self::from_static("cpp");
+    pub const CSHARP: Self = Self::from_static("csharp");
+    pub const GO: Self = Self::from_static("go");
+    pub const JAVA: Self = Self::from_static("java");
+    pub const JAVASCRIPT: Self = Self::from_static("javascript");
+    pub const KOTLIN: Self = Self::from_static("kotlin");
+    pub const PYTHON: Self = Self::from_static("python");
+    pub const RUST: Self = Self::from_static("rust");
+    pub const TYPESCRIPT: Self = Self::from_static("typescript");
+
+    /// 创建新的编程语言
+    pub fn new(language_name: impl Into<String>) -> Self {
+        Self {
+            language_name: Cow::Owned(language_name.into()),
+        }
+    }
+
+    /// 通过驻留器创建
+    ///
+    /// 名称先经驻留器去重；规范语言名称直接借用与常量相同的静态字符串，
+    /// 不再分配。`language_name` 为 `Cow<'static, str>`，无法持有 `Arc`，
+    /// 其余名称仍会复制一份，需要共享句柄时直接使用 [`LanguageInterner::intern`]
+    pub fn interned(interner: &LanguageInterner, language_name: &str) -> Self {
+        let name = interner.intern(language_name);
+        match canonical_alias(&name) {
+            Some(canonical) if canonical == &*name => Self::from_static(canonical),
+            _ => Self::new(&*name),
+        }
+    }
+
+    /// 从静态字符串创建（可用于常量）
+    pub const fn from_static(language_name: &'static str) -> Self {
+        Self {
+            language_name: Cow::Borrowed(language_name),
+        }
+    }
+
+    /// 按规范名称创建
+    ///
+    /// 名称会去除首尾空白并转为小写，常见别名（如 `rs`、`js`、`c++`）映射为规范名称
+    pub fn normalized(name: &str) -> Self {
+        let lower = name.trim().to_lowercase();
+        match canonical_alias(&lower) {
+            Some(canonical) => Self::from_static(canonical),
+            None => Self::new(lower),
+        }
+    }
+
+    /// 解析带版本后缀的语言名称
+    ///
+    /// 如 `python3`、`python2.7`、`c++17`，返回规范化后的语言和版本号；
+    /// 没有版本后缀时版本为 `None`
+    pub fn from_versioned(name: &str) -> (Self, Option<String>) {
+        let trimmed = name.trim();
+        let base = trimmed.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        let version = &trimmed[base.len()..];
+        let base = base.trim_end_matches(['-', '_', ' ']);
+        if base.is_empty() || !version.starts_with(|c: char| c.is_ascii_digit()) {
+            return (Self::normalized(trimmed), None);
+        }
+        (Self::normalized(base), Some(version.to_string()))
+    }
+
+    /// 从 Markdown 代码块信息字符串解析语言
+    ///
+    /// 接受 ```` ```rust ```` 或 `rust,no_run` 等形式，仅取第一个标记；
+    /// 信息字符串为空时返回 `None`
+    pub fn from_code_fence(info_string: &str) -> Option<Self> {
+        let info = info_string
+            .trim()
+            .trim_start_matches(['`', '~'])
+            .trim_start();
+        let token = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .next()
+            .filter(|token| !token.is_empty())?;
+        Some(Self::normalized(token))
+    }
+
+    /// 从脚本首行的 shebang 解析语言
+    ///
+    /// 支持 `#!/usr/bin/python3` 和 `#!/usr/bin/env -S node --flag` 等形式，
+    /// 解释器名称按 [`Self::from_versioned`] 去除版本号；不是 shebang 行时返回 `None`
+    pub fn from_shebang(line: &str) -> Option<Self> {
+        let command = line.trim().strip_prefix("#!")?;
+        let mut tokens = command.split_whitespace();
+        let mut program = tokens.next()?.rsplit('/').next()?;
+        if program == "env" {
+            program = tokens.find(|token| !token.starts_with('-'))?;
+        }
+        Some(Self::from_versioned(program).0).filter(|language| !language.language_name.is_empty())
+    }
+
+    /// 根据文件路径的扩展名推断语言
+    ///
+    /// 扩展名不区分大小写，与 [`Self::normalized`] 共用别名表；
+    /// 没有扩展名或扩展名未知时返回 `None`
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.trim().to_lowercase();
+        canonical_alias(&extension).map(Self::from_static)
+    }
+
+    /// 获取 highlight.js 语言类名
+    ///
+    /// 与 highlight.js 命名不一致的语言做映射，其余直接使用语言名称
+    pub fn highlightjs_class(&self) -> &str {
+        match self.language_name.as_ref() {
+            "jsx" => "javascript",
+            "tsx" => "typescript",
+            "shell" => "bash",
+            "systemverilog" => "verilog",
+            "vb" => "vbnet",
+            "terraform" => "hcl",
+            other => other,
+        }
+    }
+
+    /// 获取 tree-sitter 语法名称
+    ///
+    /// 仅映射有官方或常用 tree-sitter 语法的语言，未知语言返回 `None`
+    pub fn tree_sitter_name(&self) -> Option<&'static str> {
+        let name = match self.language_name.as_ref() {
+            "bash" | "shell" => "bash",
+            "c" => "c",
+            "cpp" => "cpp",
+            "csharp" => "c_sharp",
+            "css" => "css",
+            "go" => "go",
+            "html" => "html",
+            "java" => "java",
+            "javascript" | "jsx" => "javascript",
+            "json" => "json",
+            "kotlin" => "kotlin",
+            "php" => "php",
+            "python" => "python",
+            "ruby" => "ruby",
+            "rust" => "rust",
+            "scala" => "scala",
+            "sql" => "sql",
+            "swift" => "swift",
+            "toml" => "toml",
+            "tsx" => "tsx",
+            "typescript" => "typescript",
+            "yaml" => "yaml",
+            _ => return None,
+        };
+        Some(name)
+    }
+
+    /// 获取默认格式化命令
+    ///
+    /// 返回命令及其参数，格式化内容通过标准输入传入，无需再补充参数。
+    /// prettier 依据 `--stdin-filepath` 的扩展名推断解析器，这里传入占位文件名，
+    /// 该文件无需存在；未知语言返回 `None`
+    pub fn formatter(&self) -> Option<&'static [&'static str]> {
+        let command: &'static [&'static str] = match self.language_name.as_ref() {
+            "c" | "cpp" => &["clang-format"],
+            "csharp" => &["dotnet", "csharpier", "--write-stdout"],
+            "go" => &["gofmt"],
+            "java" => &["google-java-format", "-"],
+            "javascript" => &["prettier", "--stdin-filepath", "stdin.js"],
+            "jsx" => &["prettier", "--stdin-filepath", "stdin.jsx"],
+            "typescript" => &["prettier", "--stdin-filepath", "stdin.ts"],
+            "tsx" => &["prettier", "--stdin-filepath", "stdin.tsx"],
+            "css" => &["prettier", "--stdin-filepath", "stdin.css"],
+            "html" => &["prettier", "--stdin-filepath", "stdin.html"],
+            "json" => &["prettier", "--stdin-filepath", "stdin.json"],
+            "yaml" => &["prettier", "--stdin-filepath", "stdin.yaml"],
+            "kotlin" => &["ktlint", "--format", "--stdin"],
+            "python" => &["black", "-"],
+            "rust" => &["rustfmt"],
+            "bash" | "shell" => &["shfmt"],
+            _ => return None,
+        };
+        Some(command)
+    }
+
+    /// 去除语言名称首尾空白
+    pub fn normalize(&mut self) {
+        let trimmed = self.language_name.trim();
+        if trimmed.len() != self.language_name.len() {
+            self.language_name = Cow::Owned(trimmed.to_string());
+        }
+    }
+}
+
+impl From<ProgrammingLanguage> for String {
+    fn from(language: ProgrammingLanguage) -> Self {
+        language.language_name.into_owned()
+    }
+}
+
+/// 语言别名和文件扩展名到规范名称的映射
+///
+/// 名称解析（[`ProgrammingLanguage::normalized`]）与扩展名推断
+/// （[`ProgrammingLanguage::from_path`]）共用此表，键须为小写
+fn canonical_alias(name: &str) -> Option<&'static str> {
+    let canonical = match name {
+        "rs" | "rust" => "rust",
+        "py" | "pyi" | "python" => "python",
+        "js" | "mjs" | "cjs" | "javascript" | "node" | "nodejs" => "javascript",
+        "jsx" => "jsx",
+        "ts" | "mts" | "cts" | "typescript" => "typescript",
+        "tsx" => "tsx",
+        "c" | "h" => "c",
+        "c++" | "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => "cpp",
+        "c#" | "cs" | "csharp" => "csharp",
+        "go" | "golang" => "go",
+        "java" => "java",
+        "kt" | "kts" | "kotlin" => "kotlin",
+        "rb" | "ruby" => "ruby",
+        "php" => "php",
+        "swift" => "swift",
+        "scala" => "scala",
+        "sh" | "bash" | "zsh" | "shell" => "shell",
+        "sql" => "sql",
+        "json" => "json",
+        "yml" | "yaml" => "yaml",
+        "toml" => "toml",
+        "md" | "markdown" => "markdown",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "tf" | "terraform" => "terraform",
+        _ => return None,
+    };
+    Some(canonical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let json = serde_json::to_string(&ProgrammingLanguage::new("rust")).unwrap();
+        assert_eq!(json, r#"{"languageName":"rust"}"#);
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(ProgrammingLanguage::RUST.language_name, "rust");
+        assert_eq!(ProgrammingLanguage::PYTHON.language_name, "python");
+        assert_eq!(ProgrammingLanguage::RUST, ProgrammingLanguage::new("rust"));
+    }
+
+    #[test]
+    fn test_highlightjs_class() {
+        assert_eq!(ProgrammingLanguage::CPP.highlightjs_class(), "cpp");
+        assert_eq!(ProgrammingLanguage::CSHARP.highlightjs_class(), "csharp");
+        assert_eq!(
+            ProgrammingLanguage::new("shell").highlightjs_class(),
+            "bash"
+        );
+        assert_eq!(ProgrammingLanguage::new("zig").highlightjs_class(), "zig");
+    }
+
+    #[test]
+    fn test_interner_shares_storage() {
+        let interner = LanguageInterner::new();
+        let first = interner.intern("rust");
+        let second = interner.intern("rust");
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &interner.intern("go")));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_interned_language() {
+        let interner = LanguageInterner::new();
+        let a = ProgrammingLanguage::interned(&interner, "python");
+        let b = ProgrammingLanguage::interned(&interner, "python");
+
+        match (&a.language_name, &b.language_name) {
+            (Cow::Borrowed(x), Cow::Borrowed(y)) => assert!(std::ptr::eq(*x, *y)),
+            _ => panic!("规范语言名称应借用静态字符串"),
+        }
+        assert_eq!(a, ProgrammingLanguage::PYTHON);
+
+        let custom = ProgrammingLanguage::interned(&interner, "zig");
+        assert_eq!(custom.language_name, "zig");
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let language: ProgrammingLanguage =
+            serde_json::from_str(r#"{"languageName":"go"}"#).unwrap();
+        assert_eq!(language, ProgrammingLanguage::GO);
+    }
+
+    #[test]
+    fn test_normalized() {
+        assert_eq!(
+            ProgrammingLanguage::normalized(" Rust "),
+            ProgrammingLanguage::RUST
+        );
+        assert_eq!(
+            ProgrammingLanguage::normalized("C++"),
+            ProgrammingLanguage::CPP
+        );
+        assert_eq!(ProgrammingLanguage::normalized("Zig").language_name, "zig");
+    }
+
+    #[test]
+    fn test_from_code_fence() {
+        assert_eq!(
+            ProgrammingLanguage::from_code_fence("rust"),
+            Some(ProgrammingLanguage::RUST)
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_code_fence("```rust,no_run"),
+            Some(ProgrammingLanguage::RUST)
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_code_fence("py title=\"main.py\""),
+            Some(ProgrammingLanguage::PYTHON)
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_code_fence("``` rust"),
+            Some(ProgrammingLanguage::RUST)
+        );
+        assert_eq!(ProgrammingLanguage::from_code_fence(""), None);
+        assert_eq!(ProgrammingLanguage::from_code_fence("```"), None);
+    }
+
+    #[test]
+    fn test_from_versioned() {
+        assert_eq!(
+            ProgrammingLanguage::from_versioned("python3"),
+            (ProgrammingLanguage::PYTHON, Some("3".to_string()))
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_versioned("python2.7"),
+            (ProgrammingLanguage::PYTHON, Some("2.7".to_string()))
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_versioned("c++17"),
+            (ProgrammingLanguage::CPP, Some("17".to_string()))
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_versioned("rust"),
+            (ProgrammingLanguage::RUST, None)
+        );
+    }
+
+    #[test]
+    fn test_into_string() {
+        let name: String = ProgrammingLanguage::RUST.into();
+        assert_eq!(name, "rust");
+        assert_eq!(String::from(ProgrammingLanguage::new("zig")), "zig");
+    }
+
+    #[test]
+    fn test_tree_sitter_name() {
+        assert_eq!(
+            ProgrammingLanguage::CSHARP.tree_sitter_name(),
+            Some("c_sharp")
+        );
+        assert_eq!(ProgrammingLanguage::RUST.tree_sitter_name(), Some("rust"));
+        assert_eq!(
+            ProgrammingLanguage::new("tsx").tree_sitter_name(),
+            Some("tsx")
+        );
+        assert_eq!(
+            ProgrammingLanguage::new("brainfuck").tree_sitter_name(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_formatter() {
+        assert_eq!(
+            ProgrammingLanguage::RUST.formatter(),
+            Some(&["rustfmt"][..])
+        );
+        assert_eq!(
+            ProgrammingLanguage::PYTHON.formatter(),
+            Some(&["black", "-"][..])
+        );
+        assert_eq!(
+            ProgrammingLanguage::JAVASCRIPT.formatter(),
+            Some(&["prettier", "--stdin-filepath", "stdin.js"][..])
+        );
+        assert_eq!(
+            ProgrammingLanguage::new("json").formatter(),
+            Some(&["prettier", "--stdin-filepath", "stdin.json"][..])
+        );
+        assert_eq!(ProgrammingLanguage::new("brainfuck").formatter(), None);
+    }
+
+    #[test]
+    fn test_from_shebang() {
+        assert_eq!(
+            ProgrammingLanguage::from_shebang("#!/usr/bin/env python3"),
+            Some(ProgrammingLanguage::PYTHON)
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_shebang("#!/usr/bin/env -S node --no-warnings"),
+            Some(ProgrammingLanguage::JAVASCRIPT)
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_shebang("#!/bin/bash -e"),
+            Some(ProgrammingLanguage::new("shell"))
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_shebang("#!/usr/bin/nodejs"),
+            Some(ProgrammingLanguage::JAVASCRIPT)
+        );
+        assert_eq!(ProgrammingLanguage::from_shebang("import os"), None);
+        assert_eq!(ProgrammingLanguage::from_shebang("#!"), None);
+    }
+
+    #[test]
+    fn test_from_path() {
+        use std::path::Path;
+
+        assert_eq!(
+            ProgrammingLanguage::from_path(Path::new("src/main.rs")),
+            Some(ProgrammingLanguage::RUST)
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_path(Path::new("scripts/Build.PY")),
+            Some(ProgrammingLanguage::PYTHON)
+        );
+        assert_eq!(
+            ProgrammingLanguage::from_path(Path::new("infra/main.tf")),
+            Some(ProgrammingLanguage::normalized("terraform"))
+        );
+        // 扩展名与名称解析共用同一张别名表
+        for extension in ["kts", "pyi", "hxx", "mjs"] {
+            assert_eq!(
+                ProgrammingLanguage::from_path(Path::new(&format!("file.{}", extension))),
+                Some(ProgrammingLanguage::normalized(extension))
+            );
+        }
+        assert_eq!(ProgrammingLanguage::from_path(Path::new("Makefile")), None);
+        assert_eq!(ProgrammingLanguage::from_path(Path::new("a.unknown")), None);
+    }
+}
@@ -0,0 +1,241 @@
+//! 许可证标识定义
+//!
+//! 维护常见的 SPDX 许可证及例外标识列表，并校验 SPDX 许可证表达式
+
+/// 已知的 SPDX 许可证标识
+///
+/// 仅收录代码引用中常见的许可证，并非完整的 SPDX 列表
+pub const SPDX_LICENSE_IDS: &[&str] = &[
+    "0BSD",
+    "AFL-3.0",
+    "AGPL-3.0-only",
+    "AGPL-3.0-or-later",
+    "Apache-1.1",
+    "Apache-2.0",
+    "Artistic-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "BSD-3-Clause-Clear",
+    "BSL-1.0",
+    "CC-BY-4.0",
+    "CC-BY-SA-4.0",
+    "CC0-1.0",
+    "CDDL-1.0",
+    "ECL-2.0",
+    "EPL-1.0",
+    "EPL-2.0",
+    "EUPL-1.2",
+    "GPL-2.0-only",
+    "GPL-2.0-or-later",
+    "GPL-3.0-only",
+    "GPL-3.0-or-later",
+    "ISC",
+    "LGPL-2.1-only",
+    "LGPL-2.1-or-later",
+    "LGPL-3.0-only",
+    "LGPL-3.0-or-later",
+    "MIT",
+    "MIT-0",
+    "MPL-2.0",
+    "MS-PL",
+    "NCSA",
+    "OFL-1.1",
+    "OSL-3.0",
+    "PostgreSQL",
+    "Unicode-3.0",
+    "Unlicense",
+    "UPL-1.0",
+    "WTFPL",
+    "Zlib",
+];
+
+/// 已知的 SPDX 许可证例外标识（用于 `WITH` 之后）
+///
+/// 同样只收录常见例外
+pub const SPDX_EXCEPTION_IDS: &[&str] = &[
+    "Autoconf-exception-3.0",
+    "Bison-exception-2.2",
+    "Classpath-exception-2.0",
+    "GCC-exception-3.1",
+    "LLVM-exception",
+    "OpenJDK-assembly-exception-1.0",
+];
+
+/// 查找 SPDX 许可证标识
+///
+/// 按 SPDX 规范不区分大小写匹配，返回列表中的规范写法
+pub fn find_spdx_license(id: &str) -> Option<&'static str> {
+    let id = id.trim();
+    SPDX_LICENSE_IDS
+        .iter()
+        .copied()
+        .find(|known| known.eq_ignore_ascii_case(id))
+}
+
+/// 查找 SPDX 许可证例外标识
+///
+/// 匹配规则与 [`find_spdx_license`] 相同
+pub fn find_spdx_exception(id: &str) -> Option<&'static str> {
+    SPDX_EXCEPTION_IDS
+        .iter()
+        .copied()
+        .find(|known| known.eq_ignore_ascii_case(id))
+}
+
+/// 校验并规范化 SPDX 许可证表达式
+///
+/// 接受单个标识（可带 `+` 后缀）以及由 `AND` / `OR` / `WITH` 和括号组成的表达式，
+/// 运算符须为大写。表达式中的每个许可证标识都必须在 [`SPDX_LICENSE_IDS`] 中，
+/// `WITH` 之后的例外标识必须在 [`SPDX_EXCEPTION_IDS`] 中；标识替换为规范写法，
+/// 空白统一为单个空格。语法不合法或包含未知标识时返回 `None`
+pub fn normalize_spdx_expression(expression: &str) -> Option<String> {
+    let tokens = tokenize(expression);
+    let mut parser = ExpressionParser { tokens, pos: 0 };
+    let normalized = parser.or_expression()?;
+    (parser.pos == parser.tokens.len()).then_some(normalized)
+}
+
+/// 拆分表达式为括号和单词
+fn tokenize(expression: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (index, c) in expression.char_indices() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if let Some(begin) = start.take() {
+                tokens.push(&expression[begin..index]);
+            }
+            if !c.is_whitespace() {
+                tokens.push(&expression[index..index + 1]);
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+    if let Some(begin) = start {
+        tokens.push(&expression[begin..]);
+    }
+    tokens
+}
+
+/// SPDX 表达式递归下降解析器
+///
+/// 优先级从低到高依次为 `OR`、`AND`、`WITH`
+struct ExpressionParser<'a> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> ExpressionParser<'a> {
+    fn next_if(&mut self, expected: &str) -> bool {
+        let matched = self.tokens.get(self.pos) == Some(&expected);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn or_expression(&mut self) -> Option<String> {
+        let mut expression = self.and_expression()?;
+        while self.next_if("OR") {
+            expression = format!("{} OR {}", expression, self.and_expression()?);
+        }
+        Some(expression)
+    }
+
+    fn and_expression(&mut self) -> Option<String> {
+        let mut expression = self.with_expression()?;
+        while self.next_if("AND") {
+            expression = format!("{} AND {}", expression, self.with_expression()?);
+        }
+        Some(expression)
+    }
+
+    fn with_expression(&mut self) -> Option<String> {
+        let license = self.primary()?;
+        if self.next_if("WITH") {
+            let exception = find_spdx_exception(self.tokens.get(self.pos)?)?;
+            self.pos += 1;
+            return Some(format!("{} WITH {}", license, exception));
+        }
+        Some(license)
+    }
+
+    fn primary(&mut self) -> Option<String> {
+        if self.next_if("(") {
+            let inner = self.or_expression()?;
+            return self.next_if(")").then(|| format!("({})", inner));
+        }
+        let token = *self.tokens.get(self.pos)?;
+        if matches!(token, "AND" | "OR" | "WITH" | ")") {
+            return None;
+        }
+        let (id, plus) = match token.strip_suffix('+') {
+            Some(id) => (id, "+"),
+            None => (token, ""),
+        };
+        let id = find_spdx_license(id)?;
+        self.pos += 1;
+        Some(format!("{}{}", id, plus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_spdx_license() {
+        assert_eq!(find_spdx_license("MIT"), Some("MIT"));
+        assert_eq!(find_spdx_license("apache-2.0"), Some("Apache-2.0"));
+        assert_eq!(find_spdx_license("NotALicense"), None);
+    }
+
+    #[test]
+    fn test_normalize_spdx_expression() {
+        assert_eq!(normalize_spdx_expression("mit").as_deref(), Some("MIT"));
+        assert_eq!(
+            normalize_spdx_expression("MIT OR apache-2.0").as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(
+            normalize_spdx_expression(
+                "(MIT  OR Zlib)AND GPL-2.0-or-later WITH Classpath-exception-2.0"
+            )
+            .as_deref(),
+            Some("(MIT OR Zlib) AND GPL-2.0-or-later WITH Classpath-exception-2.0")
+        );
+        assert_eq!(
+            normalize_spdx_expression("apache-2.0+").as_deref(),
+            Some("Apache-2.0+")
+        );
+    }
+
+    #[test]
+    fn test_normalize_spdx_expression_rejects_unknown_ids() {
+        for unknown in [
+            "NotALicense",
+            "MIT OR NotALicense",
+            "(NotALicense AND MIT)",
+            "LicenseRef-Internal",
+            "GPL-2.0-only WITH NotAnException",
+        ] {
+            assert_eq!(normalize_spdx_expression(unknown), None, "{}", unknown);
+        }
+    }
+
+    #[test]
+    fn test_normalize_spdx_expression_rejects_invalid_syntax() {
+        for invalid in [
+            "",
+            "MIT OR",
+            "(MIT",
+            "MIT)",
+            "MIT Apache-2.0",
+            "AND MIT",
+            "MIT/X11",
+            "MIT or Zlib",
+        ] {
+            assert_eq!(normalize_spdx_expression(invalid), None, "{}", invalid);
+        }
+    }
+}
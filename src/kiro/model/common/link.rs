@@ -0,0 +1,580 @@
+//! 补充网页链接定义
+//!
+//! 定义响应中附带的补充网页链接及其辅助函数
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::ModelError;
+use super::reference::Reference;
+#[cfg(feature = "score-presence")]
+use super::serde_utils::Presence;
+use super::serde_utils::serialize_score_rounded;
+use super::util::{trim_in_place, trim_optional};
+use super::validate::Validate;
+
+/// 补充网页链接
+///
+/// 序列化时评分保留 [`SCORE_DECIMALS`](super::serde_utils::SCORE_DECIMALS) 位小数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "non-exhaustive", non_exhaustive)]
+pub struct SupplementaryWebLink {
+    /// 链接地址
+    pub url: String,
+    /// 链接标题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 内容摘要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+    /// 相关性评分
+    #[cfg(not(feature = "score-presence"))]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_score_rounded"
+    )]
+    pub score: Option<f64>,
+    /// 相关性评分（区分缺失与显式 null）
+    #[cfg(feature = "score-presence")]
+    #[serde(
+        default,
+        skip_serializing_if = "Presence::is_absent",
+        serialize_with = "serialize_score_rounded"
+    )]
+    pub score: Presence<f64>,
+}
+
+impl SupplementaryWebLink {
+    /// 创建新的补充网页链接
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            title: None,
+            snippet: None,
+            score: Default::default(),
+        }
+    }
+
+    /// 以仓库名作为标题创建链接
+    ///
+    /// 用于引用没有标题时构造展示链接
+    pub fn from_url_and_repo(url: impl Into<String>, repository: impl Into<String>) -> Self {
+        Self::new(url).with_title(repository)
+    }
+
+    /// 设置链接标题
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// 设置内容摘要
+    pub fn with_snippet(mut self, snippet: impl Into<String>) -> Self {
+        self.snippet = Some(snippet.into());
+        self
+    }
+
+    /// 设置相关性评分
+    pub fn with_score(mut self, score: f64) -> Self {
+        #[cfg(not(feature = "score-presence"))]
+        {
+            self.score = Some(score);
+        }
+        #[cfg(feature = "score-presence")]
+        {
+            self.score = Presence::Value(score);
+        }
+        self
+    }
+
+    /// 根据排名设置相关性评分
+    ///
+    /// 排名从 1 开始（1 为最佳），评分为 `1 - (rank - 1) / total`；
+    /// `rank` 为 0 时按 1 处理，`total` 小于 `rank` 时按 `rank` 处理
+    pub fn with_rank(self, rank: usize, total: usize) -> Self {
+        let rank = rank.max(1);
+        let total = total.max(rank);
+        self.with_score(1.0 - (rank - 1) as f64 / total as f64)
+    }
+
+    /// 获取相关性评分
+    ///
+    /// 无论是否启用 `score-presence` 特性，缺失和 null 均返回 `None`
+    pub fn score(&self) -> Option<f64> {
+        #[cfg(not(feature = "score-presence"))]
+        return self.score;
+        #[cfg(feature = "score-presence")]
+        return self.score.as_option().copied();
+    }
+
+    /// 获取链接的主机名（小写）
+    ///
+    /// URL 无法解析或没有主机部分时返回 `None`
+    pub fn host(&self) -> Option<String> {
+        let url = url::Url::parse(&self.url).ok()?;
+        url.host_str().map(|host| host.to_lowercase())
+    }
+
+    /// 判断链接是否指向指定主机（完全相同或为其子域名，不区分大小写）
+    ///
+    /// URL 无法解析或没有主机部分时返回 `false`
+    pub fn is_host(&self, host: &str) -> bool {
+        self.host().is_some_and(|own| host_matches(&own, host))
+    }
+
+    /// 将摘要截断到最多 `max_chars` 个字符
+    ///
+    /// 按字符边界截断并追加省略号 `…`（省略号不计入 `max_chars`）；未超出时保持不变
+    pub fn truncate_snippet(&mut self, max_chars: usize) {
+        if let Some(snippet) = &mut self.snippet
+            && let Some((cut, _)) = snippet.char_indices().nth(max_chars)
+        {
+            snippet.truncate(cut);
+            snippet.push('…');
+        }
+    }
+
+    /// 生成单行紧凑描述，仅包含已设置的字段
+    ///
+    /// 用于批量记录日志，比 `Debug` 输出更简短
+    pub fn compact(&self) -> String {
+        let mut parts = vec![format!("url={}", self.url)];
+        if let Some(title) = &self.title {
+            parts.push(format!("title={}", title));
+        }
+        if let Some(snippet) = &self.snippet {
+            parts.push(format!("snippet={}", snippet));
+        }
+        if let Some(score) = self.score() {
+            parts.push(format!("score={}", score));
+        }
+        format!("Link({})", parts.join(", "))
+    }
+
+    /// 规范化字符串字段
+    ///
+    /// 去除首尾空白，可选字段去除后为空则置为 `None`
+    pub fn normalize(&mut self) {
+        trim_in_place(&mut self.url);
+        trim_optional(&mut self.title);
+        trim_optional(&mut self.snippet);
+    }
+}
+
+impl Validate for SupplementaryWebLink {
+    /// 校验链接
+    ///
+    /// 链接地址必须是可解析的 URL
+    fn validate(&self) -> Result<(), ModelError> {
+        if self.url.trim().is_empty() {
+            return Err(ModelError::EmptyField("url"));
+        }
+        url::Url::parse(&self.url).map_err(|_| ModelError::InvalidUrl(self.url.clone()))?;
+        Ok(())
+    }
+}
+
+/// 将引用转换为补充链接，便于与链接统一展示
+///
+/// 仓库名作为标题，附加信息作为摘要；引用没有链接时为 `None`
+impl From<&Reference> for Option<SupplementaryWebLink> {
+    fn from(reference: &Reference) -> Self {
+        let mut link = SupplementaryWebLink::new(reference.url.clone()?);
+        link.title = reference.repository.clone();
+        link.snippet = reference.information.clone();
+        Some(link)
+    }
+}
+
+/// 获取评分最高的链接
+///
+/// 无评分的链接视为最低；评分相同时取最先出现的链接
+#[allow(dead_code)]
+pub fn best_link(links: &[SupplementaryWebLink]) -> Option<&SupplementaryWebLink> {
+    links.iter().reduce(|best, link| {
+        let better = match (link.score(), best.score()) {
+            (Some(score), Some(best_score)) => score > best_score,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if better { link } else { best }
+    })
+}
+
+/// 移除评分低于阈值的链接
+///
+/// 评分等于阈值的链接会保留；`keep_unscored` 控制是否保留没有评分的链接
+#[allow(dead_code)]
+pub fn retain_links_above(
+    links: &mut Vec<SupplementaryWebLink>,
+    threshold: f64,
+    keep_unscored: bool,
+) {
+    links.retain(|link| {
+        link.score()
+            .map_or(keep_unscored, |score| score >= threshold)
+    });
+}
+
+/// 判断主机名是否匹配允许的主机（完全相同或为其子域名，不区分大小写）
+fn host_matches(host: &str, allowed: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    let allowed = allowed.trim().trim_end_matches('.').to_ascii_lowercase();
+    host == allowed || host.ends_with(&format!(".{}", allowed))
+}
+
+/// 移除指向指定主机（含子域名）的链接
+///
+/// 用于过滤指回用户自身仓库等自引用链接，其余链接保持原有顺序
+#[allow(dead_code)]
+pub fn drop_links_from_host(links: &mut Vec<SupplementaryWebLink>, host: &str) {
+    links.retain(|link| !link.is_host(host));
+}
+
+/// 按主机白名单将链接拆分为内部链接和外部链接
+///
+/// 主机与白名单中任一项相同或为其子域名时视为内部链接；
+/// URL 无法解析的链接归为外部链接。返回 `(内部, 外部)`
+#[allow(dead_code)]
+pub fn partition_links_by_host(
+    links: Vec<SupplementaryWebLink>,
+    internal_hosts: &[&str],
+) -> (Vec<SupplementaryWebLink>, Vec<SupplementaryWebLink>) {
+    links.into_iter().partition(|link| {
+        link.host().is_some_and(|host| {
+            internal_hosts
+                .iter()
+                .any(|allowed| host_matches(&host, allowed))
+        })
+    })
+}
+
+/// 获取评分最高的前 `n` 个链接
+///
+/// 按评分降序排列，未评分的链接排在最后；评分相同时保持原有顺序
+#[allow(dead_code)]
+pub fn top_n_links(links: &[SupplementaryWebLink], n: usize) -> Vec<&SupplementaryWebLink> {
+    let mut sorted: Vec<&SupplementaryWebLink> = links.iter().collect();
+    sorted.sort_by(|a, b| match (a.score(), b.score()) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    sorted.truncate(n);
+    sorted
+}
+
+/// 按主机名聚合链接的平均评分
+///
+/// 未评分或无法解析主机名的链接不参与统计；结果按平均分降序排列，
+/// 平均分相同时按主机名排序
+#[allow(dead_code)]
+pub fn rank_domains(links: &[SupplementaryWebLink]) -> Vec<(String, f64)> {
+    let mut totals: HashMap<String, (f64, usize)> = HashMap::new();
+    for link in links {
+        if let (Some(host), Some(score)) = (link.host(), link.score()) {
+            let entry = totals.entry(host).or_default();
+            entry.0 += score;
+            entry.1 += 1;
+        }
+    }
+
+    let mut ranked: Vec<(String, f64)> = totals
+        .into_iter()
+        .map(|(host, (sum, count))| (host, sum / count as f64))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_sets_all_fields() {
+        let link = SupplementaryWebLink::new("https://example.com")
+            .with_title("Example")
+            .with_snippet("snippet")
+            .with_score(0.5);
+
+        assert_eq!(link.url, "https://example.com");
+        assert_eq!(link.title.as_deref(), Some("Example"));
+        assert_eq!(link.snippet.as_deref(), Some("snippet"));
+        assert_eq!(link.score(), Some(0.5));
+    }
+
+    #[test]
+    fn test_compact() {
+        let minimal = SupplementaryWebLink::new("https://a.example");
+        assert_eq!(minimal.compact(), "Link(url=https://a.example)");
+
+        let full = SupplementaryWebLink::new("https://a.example")
+            .with_title("A")
+            .with_snippet("text")
+            .with_score(0.5);
+        assert_eq!(
+            full.compact(),
+            "Link(url=https://a.example, title=A, snippet=text, score=0.5)"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            SupplementaryWebLink::new("https://a.example")
+                .validate()
+                .is_ok()
+        );
+        assert_eq!(
+            SupplementaryWebLink::new("").validate(),
+            Err(ModelError::EmptyField("url"))
+        );
+        assert_eq!(
+            SupplementaryWebLink::new("not a url").validate(),
+            Err(ModelError::InvalidUrl("not a url".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_from_url_and_repo() {
+        let link = SupplementaryWebLink::from_url_and_repo("https://github.com/o/r", "o/r");
+        assert_eq!(link.url, "https://github.com/o/r");
+        assert_eq!(link.title.as_deref(), Some("o/r"));
+        assert_eq!(link.snippet, None);
+    }
+
+    #[test]
+    fn test_truncate_snippet() {
+        let mut link = SupplementaryWebLink::new("https://a").with_snippet("这是一段很长的摘要");
+        link.truncate_snippet(4);
+        assert_eq!(link.snippet.as_deref(), Some("这是一段…"));
+
+        let mut link = SupplementaryWebLink::new("https://a").with_snippet("short");
+        link.truncate_snippet(5);
+        assert_eq!(link.snippet.as_deref(), Some("short"));
+    }
+
+    #[test]
+    fn test_with_rank() {
+        let best = SupplementaryWebLink::new("https://a").with_rank(1, 4);
+        let worst = SupplementaryWebLink::new("https://b").with_rank(4, 4);
+
+        assert_eq!(best.score(), Some(1.0));
+        assert_eq!(worst.score(), Some(0.25));
+        assert!(best.score() > worst.score());
+    }
+
+    #[test]
+    fn test_host() {
+        let link = SupplementaryWebLink::new("https://Docs.RS/serde/latest");
+        assert_eq!(link.host().as_deref(), Some("docs.rs"));
+    }
+
+    #[test]
+    fn test_host_with_port() {
+        let link = SupplementaryWebLink::new("http://localhost:8080/path");
+        assert_eq!(link.host().as_deref(), Some("localhost"));
+    }
+
+    #[test]
+    fn test_host_unparseable() {
+        let link = SupplementaryWebLink::new("not a url");
+        assert_eq!(link.host(), None);
+    }
+
+    #[test]
+    fn test_best_link() {
+        let links = vec![
+            SupplementaryWebLink::new("https://a.example").with_score(0.2),
+            SupplementaryWebLink::new("https://b.example"),
+            SupplementaryWebLink::new("https://c.example").with_score(0.9),
+            SupplementaryWebLink::new("https://d.example").with_score(0.9),
+        ];
+        assert_eq!(best_link(&links).unwrap().url, "https://c.example");
+    }
+
+    #[test]
+    fn test_best_link_all_unscored() {
+        let links = vec![
+            SupplementaryWebLink::new("https://a.example"),
+            SupplementaryWebLink::new("https://b.example"),
+        ];
+        assert_eq!(best_link(&links).unwrap().url, "https://a.example");
+    }
+
+    #[test]
+    fn test_best_link_empty() {
+        assert!(best_link(&[]).is_none());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut link = SupplementaryWebLink::new(" https://x ")
+            .with_title("  ")
+            .with_snippet(" snippet ");
+        link.normalize();
+
+        assert_eq!(link.url, "https://x");
+        assert_eq!(link.title, None);
+        assert_eq!(link.snippet.as_deref(), Some("snippet"));
+    }
+
+    #[test]
+    fn test_retain_links_above() {
+        let links = vec![
+            SupplementaryWebLink::new("https://low.example").with_score(0.3),
+            SupplementaryWebLink::new("https://high.example").with_score(0.7),
+            SupplementaryWebLink::new("https://none.example"),
+        ];
+
+        let mut kept = links.clone();
+        retain_links_above(&mut kept, 0.5, true);
+        let urls: Vec<&str> = kept.iter().map(|link| link.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://high.example", "https://none.example"]);
+
+        let mut kept = links;
+        retain_links_above(&mut kept, 0.5, false);
+        let urls: Vec<&str> = kept.iter().map(|link| link.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://high.example"]);
+    }
+
+    #[cfg(feature = "score-presence")]
+    #[test]
+    fn test_score_presence() {
+        let parse = |json| serde_json::from_str::<SupplementaryWebLink>(json).unwrap();
+
+        let absent = parse(r#"{"url":"https://a"}"#);
+        let null = parse(r#"{"url":"https://a","score":null}"#);
+        let value = parse(r#"{"url":"https://a","score":0.5}"#);
+
+        assert_eq!(absent.score, Presence::Absent);
+        assert_eq!(null.score, Presence::Null);
+        assert_eq!(value.score, Presence::Value(0.5));
+        assert_eq!(null.score(), None);
+        assert_eq!(value.score(), Some(0.5));
+    }
+
+    #[test]
+    fn test_partition_links_by_host() {
+        let links = vec![
+            SupplementaryWebLink::new("https://wiki.corp.example/page"),
+            SupplementaryWebLink::new("https://docs.rs/serde"),
+            SupplementaryWebLink::new("https://CORP.example/"),
+            SupplementaryWebLink::new("https://notcorp.example/"),
+        ];
+
+        let (internal, external) = partition_links_by_host(links, &["corp.example"]);
+
+        let urls = |links: &[SupplementaryWebLink]| -> Vec<String> {
+            links.iter().map(|link| link.url.clone()).collect()
+        };
+        assert_eq!(
+            urls(&internal),
+            vec!["https://wiki.corp.example/page", "https://CORP.example/"]
+        );
+        assert_eq!(
+            urls(&external),
+            vec!["https://docs.rs/serde", "https://notcorp.example/"]
+        );
+    }
+
+    #[test]
+    fn test_rank_domains() {
+        let links = vec![
+            SupplementaryWebLink::new("https://docs.rs/serde").with_score(0.4),
+            SupplementaryWebLink::new("https://github.com/a").with_score(0.9),
+            SupplementaryWebLink::new("https://docs.rs/tokio").with_score(0.6),
+            SupplementaryWebLink::new("https://github.com/b").with_score(0.7),
+            SupplementaryWebLink::new("https://github.com/c"),
+        ];
+        let ranked = rank_domains(&links);
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].0, "github.com");
+        assert!((ranked[0].1 - 0.8).abs() < 1e-9);
+        assert_eq!(ranked[1].0, "docs.rs");
+        assert!((ranked[1].1 - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_from_reference() {
+        let reference = Reference::new()
+            .with_url("https://github.com/owner/repo")
+            .with_repository("owner/repo")
+            .with_information("helper function");
+        let link: Option<SupplementaryWebLink> = (&reference).into();
+        let link = link.unwrap();
+
+        assert_eq!(link.url, "https://github.com/owner/repo");
+        assert_eq!(link.title.as_deref(), Some("owner/repo"));
+        assert_eq!(link.snippet.as_deref(), Some("helper function"));
+        assert_eq!(link.score(), None);
+
+        let bare = Reference::new().with_repository("owner/repo");
+        assert_eq!(Option::<SupplementaryWebLink>::from(&bare), None);
+    }
+
+    #[test]
+    fn test_top_n_links() {
+        let links = vec![
+            SupplementaryWebLink::new("https://a.example").with_score(0.2),
+            SupplementaryWebLink::new("https://b.example"),
+            SupplementaryWebLink::new("https://c.example").with_score(0.9),
+            SupplementaryWebLink::new("https://d.example").with_score(0.5),
+            SupplementaryWebLink::new("https://e.example").with_score(0.7),
+        ];
+        let top: Vec<&str> = top_n_links(&links, 2)
+            .iter()
+            .map(|link| link.url.as_str())
+            .collect();
+
+        assert_eq!(top, vec!["https://c.example", "https://e.example"]);
+        assert_eq!(top_n_links(&links, 10).len(), 5);
+        assert_eq!(top_n_links(&links, 10)[4].url, "https://b.example");
+    }
+
+    #[test]
+    fn test_is_host() {
+        let link = SupplementaryWebLink::new("https://Git.Example.com/me/repo");
+
+        assert!(link.is_host("git.example.com"));
+        assert!(link.is_host("example.com"));
+        assert!(!link.is_host("github.com"));
+        assert!(!SupplementaryWebLink::new("not a url").is_host("example.com"));
+    }
+
+    #[test]
+    fn test_drop_links_from_host() {
+        let mut links = vec![
+            SupplementaryWebLink::new("https://git.example.com/me/repo"),
+            SupplementaryWebLink::new("https://docs.rs/serde"),
+            SupplementaryWebLink::new("https://example.com/"),
+            SupplementaryWebLink::new("https://notexample.com/"),
+        ];
+        drop_links_from_host(&mut links, "example.com");
+        let urls: Vec<&str> = links.iter().map(|link| link.url.as_str()).collect();
+
+        assert_eq!(
+            urls,
+            vec!["https://docs.rs/serde", "https://notexample.com/"]
+        );
+    }
+
+    #[test]
+    fn test_score_serialized_rounded() {
+        let link = SupplementaryWebLink::new("https://example.com").with_score(0.123456789);
+
+        assert_eq!(
+            serde_json::to_string(&link).unwrap(),
+            r#"{"url":"https://example.com","score":0.1235}"#
+        );
+        // 反序列化保留原始精度
+        let parsed: SupplementaryWebLink =
+            serde_json::from_str(r#"{"url":"https://example.com","score":0.123456789}"#).unwrap();
+        assert_eq!(parsed.score(), Some(0.123456789));
+    }
+}
@@ -0,0 +1,427 @@
+//! 响应元数据定义
+//!
+//! 汇总一次响应中附带的补充链接、代码引用和后续提示
+
+use std::collections::HashSet;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::error::ParseError;
+use super::followup::FollowupPrompt;
+use super::link::SupplementaryWebLink;
+use super::parse::parse_with_context;
+use super::reference::Reference;
+use super::serde_utils::deserialize_null_as_default;
+use super::util::canonicalize_url;
+
+/// 补充链接的线上字段名
+pub(crate) const SUPPLEMENTARY_WEB_LINKS_KEY: &str = "supplementaryWebLinks";
+/// 代码引用的线上字段名
+pub(crate) const REFERENCES_KEY: &str = "references";
+/// 后续提示的线上字段名
+pub(crate) const FOLLOWUP_PROMPTS_KEY: &str = "followupPrompts";
+
+/// 响应元数据
+///
+/// 字段名须与 [`SUPPLEMENTARY_WEB_LINKS_KEY`] 等常量保持一致，由线上字段名测试锁定；
+/// 各数组字段缺失或为 `null` 时均视为空数组
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseMetadata {
+    /// 补充网页链接
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_as_default",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub supplementary_web_links: Vec<SupplementaryWebLink>,
+    /// 代码引用
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_as_default",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub references: Vec<Reference>,
+    /// 后续提示
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_as_default",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub followup_prompts: Vec<FollowupPrompt>,
+}
+
+/// 响应元数据摘要
+///
+/// 只包含计数，不含具体数据，用于日志和遥测
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataSummary {
+    /// 补充链接数量
+    pub link_count: usize,
+    /// 代码引用数量
+    pub reference_count: usize,
+    /// 后续提示数量
+    pub followup_count: usize,
+    /// 所有 URL 中不同主机名的数量
+    pub unique_domains: usize,
+}
+
+#[allow(dead_code)]
+impl ResponseMetadata {
+    /// 创建空的响应元数据
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 从三个 JSON 数组字符串构造元数据
+    ///
+    /// 依次解析补充链接、代码引用和后续提示，`null` 视为空数组；
+    /// 出错时字段路径以对应的顶层字段名开头
+    pub fn from_json(
+        links_json: &str,
+        references_json: &str,
+        followups_json: &str,
+    ) -> Result<Self, ParseError> {
+        Ok(Self {
+            supplementary_web_links: parse_array(links_json, SUPPLEMENTARY_WEB_LINKS_KEY)?,
+            references: parse_array(references_json, REFERENCES_KEY)?,
+            followup_prompts: parse_array(followups_json, FOLLOWUP_PROMPTS_KEY)?,
+        })
+    }
+
+    /// 合并另一份元数据并去重
+    ///
+    /// 链接按 url 去重，引用按 [`Reference::content_id`] 和内容范围去重
+    /// （同一来源的不同引用范围都会保留），后续提示按内容去重；均保留最先出现的条目
+    pub fn merge(&mut self, other: ResponseMetadata) {
+        self.supplementary_web_links
+            .extend(other.supplementary_web_links);
+        self.references.extend(other.references);
+        self.followup_prompts.extend(other.followup_prompts);
+
+        let mut seen_urls = HashSet::new();
+        self.supplementary_web_links
+            .retain(|link| seen_urls.insert(link.url.clone()));
+        let mut seen_ids = HashSet::new();
+        self.references.retain(|reference| {
+            seen_ids.insert((
+                reference.content_id(),
+                reference.recommendation_content_span,
+            ))
+        });
+        let mut seen_contents = HashSet::new();
+        self.followup_prompts
+            .retain(|prompt| seen_contents.insert(prompt.content.clone()));
+    }
+
+    /// 生成用于日志的摘要
+    ///
+    /// 主机名统计范围与 [`collect_urls`] 相同，无法解析的 URL 不计入
+    pub fn summary(&self) -> MetadataSummary {
+        let unique_domains = collect_urls(self)
+            .iter()
+            .filter_map(|url| url::Url::parse(url).ok()?.host_str().map(str::to_lowercase))
+            .collect::<HashSet<_>>()
+            .len();
+        MetadataSummary {
+            link_count: self.supplementary_web_links.len(),
+            reference_count: self.references.len(),
+            followup_count: self.followup_prompts.len(),
+            unique_domains,
+        }
+    }
+
+    /// 统一规范化所有 URL
+    ///
+    /// 对补充链接、引用及其错过的替代方案的 url 一并执行主机名小写和去除末尾斜杠
+    pub fn normalize_urls(&mut self) {
+        for link in &mut self.supplementary_web_links {
+            link.url = canonicalize_url(&link.url);
+        }
+        for reference in &mut self.references {
+            if let Some(url) = &mut reference.url {
+                *url = canonicalize_url(url);
+            }
+            if let Some(alt) = &mut reference.most_relevant_missed_alternative {
+                alt.url = canonicalize_url(&alt.url);
+            }
+        }
+    }
+
+    /// 移除无法解析的 URL
+    ///
+    /// 删除 url 无效的补充链接，清空无效的引用 url，
+    /// 并移除 url 无效的错过的替代方案
+    pub fn retain_valid_urls(&mut self) {
+        let is_valid = |url: &str| url::Url::parse(url).is_ok();
+
+        self.supplementary_web_links
+            .retain(|link| is_valid(&link.url));
+        for reference in &mut self.references {
+            if reference.url.as_deref().is_some_and(|url| !is_valid(url)) {
+                reference.url = None;
+            }
+            if reference
+                .most_relevant_missed_alternative
+                .as_ref()
+                .is_some_and(|alt| !is_valid(&alt.url))
+            {
+                reference.most_relevant_missed_alternative = None;
+            }
+        }
+    }
+}
+
+/// 解析 JSON 数组，并在错误路径前加上字段名
+fn parse_array<T: DeserializeOwned>(json: &str, field: &str) -> Result<Vec<T>, ParseError> {
+    parse_with_context::<Option<Vec<T>>>(json)
+        .map(Option::unwrap_or_default)
+        .map_err(|mut e| {
+            e.path = if e.path == "." {
+                field.to_string()
+            } else {
+                format!("{}{}", field, e.path)
+            };
+            e
+        })
+}
+
+/// 收集响应元数据中出现的所有 URL
+///
+/// 依次遍历补充链接、引用及其错过的替代方案，按首次出现顺序去重
+pub fn collect_urls(meta: &ResponseMetadata) -> Vec<String> {
+    let links = meta
+        .supplementary_web_links
+        .iter()
+        .map(|link| link.url.as_str());
+    let references = meta.references.iter().flat_map(|reference| {
+        let alternative = reference
+            .most_relevant_missed_alternative
+            .as_ref()
+            .map(|alt| alt.url.as_str());
+        reference.url.as_deref().into_iter().chain(alternative)
+    });
+
+    let mut seen = HashSet::new();
+    links
+        .chain(references)
+        .filter(|url| seen.insert(*url))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kiro::model::common::reference::MostRelevantMissedAlternative;
+
+    #[test]
+    fn test_collect_urls() {
+        let mut reference = Reference::new().with_url("https://b.example");
+        reference.most_relevant_missed_alternative = Some(MostRelevantMissedAlternative {
+            url: "https://c.example".to_string(),
+            license_name: None,
+            repository: None,
+        });
+
+        let meta = ResponseMetadata {
+            supplementary_web_links: vec![
+                SupplementaryWebLink::new("https://a.example"),
+                SupplementaryWebLink::new("https://b.example"),
+            ],
+            references: vec![reference, Reference::new().with_url("https://a.example")],
+            followup_prompts: Vec::new(),
+        };
+
+        assert_eq!(
+            collect_urls(&meta),
+            vec![
+                "https://a.example",
+                "https://b.example",
+                "https://c.example"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut first = ResponseMetadata {
+            supplementary_web_links: vec![SupplementaryWebLink::new("https://a.example")],
+            references: vec![
+                Reference::new()
+                    .with_repository("o/a")
+                    .with_span_range(0..5),
+            ],
+            followup_prompts: vec![FollowupPrompt::new("more")],
+        };
+        let second = ResponseMetadata {
+            supplementary_web_links: vec![
+                SupplementaryWebLink::new("https://a.example"),
+                SupplementaryWebLink::new("https://b.example"),
+            ],
+            references: vec![
+                Reference::new()
+                    .with_repository("o/a")
+                    .with_span_range(10..15),
+                Reference::new().with_repository("o/b"),
+            ],
+            followup_prompts: vec![FollowupPrompt::new("more"), FollowupPrompt::new("tests")],
+        };
+
+        first.merge(second);
+
+        let urls: Vec<&str> = first
+            .supplementary_web_links
+            .iter()
+            .map(|link| link.url.as_str())
+            .collect();
+        assert_eq!(urls, vec!["https://a.example", "https://b.example"]);
+        assert_eq!(first.references.len(), 3);
+        assert_eq!(
+            first.references[0].recommendation_content_span,
+            Some(crate::kiro::model::common::span::ContentSpan::new(0, 5))
+        );
+        let contents: Vec<&str> = first
+            .followup_prompts
+            .iter()
+            .map(|prompt| prompt.content.as_str())
+            .collect();
+        assert_eq!(contents, vec!["more", "tests"]);
+    }
+
+    #[test]
+    fn test_merge_keeps_distinct_spans_of_same_source() {
+        let reference = Reference::new()
+            .with_url("https://github.com/o/a")
+            .with_repository("o/a");
+        let mut first = ResponseMetadata {
+            references: vec![reference.clone().with_span_range(0..5)],
+            ..ResponseMetadata::new()
+        };
+        let second = ResponseMetadata {
+            references: vec![
+                reference.clone().with_span_range(0..5),
+                reference.clone().with_span_range(20..30),
+            ],
+            ..ResponseMetadata::new()
+        };
+
+        first.merge(second);
+
+        let spans: Vec<_> = first
+            .references
+            .iter()
+            .map(|reference| reference.recommendation_content_span)
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                Some(crate::kiro::model::common::span::ContentSpan::new(0, 5)),
+                Some(crate::kiro::model::common::span::ContentSpan::new(20, 30)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_json() {
+        let meta = ResponseMetadata::from_json(
+            r#"[{"url":"https://example.com","title":"Example"}]"#,
+            r#"[{"repository":"owner/repo"},{"url":"https://github.com/a/b"}]"#,
+            r#"[{"content":"Write tests","userIntent":"GENERATE_UNIT_TESTS"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(meta.supplementary_web_links.len(), 1);
+        assert_eq!(meta.references.len(), 2);
+        assert_eq!(meta.followup_prompts[0].content, "Write tests");
+    }
+
+    #[test]
+    fn test_from_json_error_path() {
+        let err = ResponseMetadata::from_json("[]", r#"[{"url":1}]"#, "[]").unwrap_err();
+        assert_eq!(err.path, "references[0].url");
+    }
+
+    #[test]
+    fn test_retain_valid_urls() {
+        let mut meta = ResponseMetadata {
+            supplementary_web_links: vec![
+                SupplementaryWebLink::new("https://example.com"),
+                SupplementaryWebLink::new("not a url"),
+            ],
+            references: vec![
+                Reference::new().with_url("https://github.com/a/b"),
+                Reference::new().with_url("::broken").with_repository("c/d"),
+            ],
+            followup_prompts: Vec::new(),
+        };
+        meta.retain_valid_urls();
+
+        assert_eq!(meta.supplementary_web_links.len(), 1);
+        assert_eq!(meta.supplementary_web_links[0].url, "https://example.com");
+        assert_eq!(meta.references.len(), 2);
+        assert_eq!(
+            meta.references[0].url.as_deref(),
+            Some("https://github.com/a/b")
+        );
+        assert_eq!(meta.references[1].url, None);
+        assert_eq!(meta.references[1].repository.as_deref(), Some("c/d"));
+    }
+
+    #[test]
+    fn test_summary() {
+        let meta = ResponseMetadata {
+            supplementary_web_links: vec![
+                SupplementaryWebLink::new("https://docs.rs/serde"),
+                SupplementaryWebLink::new("https://Docs.rs/tokio"),
+            ],
+            references: vec![
+                Reference::new().with_url("https://github.com/a/b"),
+                Reference::new().with_repository("c/d"),
+                Reference::new().with_url("not a url"),
+            ],
+            followup_prompts: vec![FollowupPrompt::new("Explain")],
+        };
+
+        assert_eq!(
+            meta.summary(),
+            MetadataSummary {
+                link_count: 2,
+                reference_count: 3,
+                followup_count: 1,
+                unique_domains: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalize_urls() {
+        let mut reference = Reference::new().with_url("https://GitHub.com/a/b/");
+        reference.most_relevant_missed_alternative = Some(MostRelevantMissedAlternative {
+            url: "https://GITHUB.COM/c/d//".to_string(),
+            license_name: None,
+            repository: None,
+        });
+        let mut meta = ResponseMetadata {
+            supplementary_web_links: vec![SupplementaryWebLink::new("https://Docs.RS/")],
+            references: vec![reference],
+            followup_prompts: Vec::new(),
+        };
+        meta.normalize_urls();
+
+        assert_eq!(meta.supplementary_web_links[0].url, "https://docs.rs");
+        assert_eq!(
+            meta.references[0].url.as_deref(),
+            Some("https://github.com/a/b")
+        );
+        assert_eq!(
+            meta.references[0]
+                .most_relevant_missed_alternative
+                .as_ref()
+                .map(|alt| alt.url.as_str()),
+            Some("https://github.com/c/d")
+        );
+    }
+}
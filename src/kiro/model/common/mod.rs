@@ -1,4 +1,148 @@
 //! 共享类型模块
 //!
-//! 此模块已简化，移除了未使用的类型定义。
-//! 如果将来需要扩展，可以在此添加新的共享类型。
+//! 包含 Kiro API 响应元数据中复用的类型（后续提示、引用、链接等）
+//! 以及围绕这些类型的辅助函数。
+//!
+//! 这些类型主要面向集成方使用，二进制内部尚未引用的入口以 `#[allow(dead_code)]` 单独标注。
+
+pub mod citation;
+pub mod coverage;
+pub mod customization;
+pub mod enums;
+pub mod error;
+pub mod followup;
+pub mod language;
+pub mod license;
+pub mod link;
+pub mod metadata;
+pub mod parse;
+pub mod provenance;
+pub mod query;
+pub mod reference;
+pub mod serde_utils;
+pub mod span;
+pub mod text;
+pub mod util;
+pub mod validate;
+
+#[cfg(test)]
+mod tests {
+    //! 线上字段名回归测试
+    //!
+    //! 序列化完整填充的实例并锁定 JSON 键集合，防止字段重命名悄悄破坏兼容性
+
+    use std::collections::BTreeSet;
+
+    use serde::Serialize;
+
+    use super::customization::Customization;
+    use super::enums::UserIntent;
+    use super::followup::FollowupPrompt;
+    use super::language::ProgrammingLanguage;
+    use super::link::SupplementaryWebLink;
+    use super::metadata::ResponseMetadata;
+    use super::query::CodeQuery;
+    use super::reference::{MostRelevantMissedAlternative, Reference};
+    use super::span::ContentSpan;
+
+    fn keys<T: Serialize>(value: &T) -> BTreeSet<String> {
+        match serde_json::to_value(value).unwrap() {
+            serde_json::Value::Object(map) => map.keys().cloned().collect(),
+            other => panic!("期望 JSON 对象, 实际: {}", other),
+        }
+    }
+
+    fn set(expected: &[&str]) -> BTreeSet<String> {
+        expected.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn test_reference_keys() {
+        let reference = Reference::new()
+            .with_license_name("MIT")
+            .with_repository("owner/repo")
+            .with_url("https://example.com")
+            .with_information("info")
+            .with_span(ContentSpan::new(0, 1))
+            .with_most_relevant_missed_alternative(MostRelevantMissedAlternative {
+                url: "https://alt.example".to_string(),
+                license_name: Some("MIT".to_string()),
+                repository: Some("owner/alt".to_string()),
+            });
+
+        assert_eq!(
+            keys(&reference),
+            set(&[
+                "licenseName",
+                "repository",
+                "url",
+                "information",
+                "recommendationContentSpan",
+                "mostRelevantMissedAlternative",
+            ])
+        );
+        assert_eq!(
+            keys(reference.most_relevant_missed_alternative.as_ref().unwrap()),
+            set(&["url", "licenseName", "repository"])
+        );
+        assert_eq!(keys(&ContentSpan::new(0, 1)), set(&["start", "end"]));
+    }
+
+    #[test]
+    fn test_code_query_keys() {
+        let query = CodeQuery::new("q-1")
+            .with_programming_language(ProgrammingLanguage::RUST)
+            .with_user_input_message_id("m-1");
+
+        assert_eq!(
+            keys(&query),
+            set(&["codeQueryId", "programmingLanguage", "userInputMessageId"])
+        );
+    }
+
+    #[test]
+    fn test_supplementary_web_link_keys() {
+        let link = SupplementaryWebLink::new("https://example.com")
+            .with_title("title")
+            .with_snippet("snippet")
+            .with_score(0.5);
+
+        assert_eq!(keys(&link), set(&["url", "title", "snippet", "score"]));
+    }
+
+    #[test]
+    fn test_followup_prompt_keys() {
+        let prompt = FollowupPrompt::new("more").with_user_intent(UserIntent::ShowExamples);
+
+        assert_eq!(keys(&prompt), set(&["content", "userIntent"]));
+    }
+
+    #[test]
+    fn test_programming_language_keys() {
+        assert_eq!(keys(&ProgrammingLanguage::RUST), set(&["languageName"]));
+    }
+
+    #[test]
+    fn test_customization_keys() {
+        let customization =
+            Customization::new("arn:aws:codewhisperer:us-east-1:123:customization/x")
+                .with_name("name")
+                .with_description("description");
+
+        assert_eq!(keys(&customization), set(&["arn", "name", "description"]));
+    }
+
+    #[test]
+    fn test_response_metadata_keys() {
+        let meta = ResponseMetadata {
+            supplementary_web_links: vec![SupplementaryWebLink::new("https://example.com")],
+            references: vec![Reference::new().with_repository("owner/repo")],
+            followup_prompts: vec![FollowupPrompt::new("more")],
+        };
+
+        assert_eq!(
+            keys(&meta),
+            set(&["supplementaryWebLinks", "references", "followupPrompts"])
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! 共享类型解析辅助
+//!
+//! 在 serde 反序列化之上附加字段路径和输入片段等上下文
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::Deserialize;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use serde_json::Value;
+
+use super::error::ParseError;
+use super::metadata::{
+    FOLLOWUP_PROMPTS_KEY, REFERENCES_KEY, ResponseMetadata, SUPPLEMENTARY_WEB_LINKS_KEY,
+};
+use super::reference::Reference;
+
+/// 错误片段在出错位置前后各保留的字符数
+const SNIPPET_RADIUS: usize = 20;
+
+/// 反序列化 JSON，出错时附带字段路径和输入片段
+pub(crate) fn parse_with_context<T: DeserializeOwned>(json: &str) -> Result<T, ParseError> {
+    parse_seed_with_context(json, PhantomData)
+}
+
+/// 使用 [`DeserializeSeed`] 反序列化 JSON，出错时附带字段路径和输入片段
+///
+/// 与 `serde_json::from_str` 一致，值之后只允许出现空白，多余字符视为错误
+fn parse_seed_with_context<'de, S: DeserializeSeed<'de>>(
+    json: &'de str,
+    seed: S,
+) -> Result<S::Value, ParseError> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let mut track = serde_path_to_error::Track::new();
+    let value = seed
+        .deserialize(serde_path_to_error::Deserializer::new(
+            &mut deserializer,
+            &mut track,
+        ))
+        .map_err(|source| context_error(json, track.path().to_string(), source))?;
+    deserializer
+        .end()
+        .map_err(|source| context_error(json, ".".to_string(), source))?;
+    Ok(value)
+}
+
+/// 为 serde 错误附加字段路径和输入片段
+fn context_error(json: &str, path: String, source: serde_json::Error) -> ParseError {
+    ParseError {
+        path,
+        snippet: snippet_at(json, source.line(), source.column()),
+        source,
+    }
+}
+
+/// 截取出错位置附近的输入片段
+fn snippet_at(input: &str, line: usize, column: usize) -> String {
+    let Some(text) = input.lines().nth(line.saturating_sub(1)) else {
+        return String::new();
+    };
+    let start = column.saturating_sub(SNIPPET_RADIUS + 1);
+    text.chars().skip(start).take(SNIPPET_RADIUS * 2).collect()
+}
+
+/// 解析单个代码引用
+#[allow(dead_code)]
+pub fn parse_reference(json: &str) -> Result<Reference, ParseError> {
+    parse_with_context(json)
+}
+
+/// 宽松解析引用数组
+///
+/// 逐个元素反序列化，返回成功解析的引用以及解析失败的元素索引。
+///
+/// 外层 `Result` 用于输入本身不是合法 JSON 数组的情况：此时没有元素，
+/// 失败索引无从表达，若返回空结果会与合法的空数组 `[]` 无法区分，
+/// 因此返回带上下文的 [`ParseError`]
+#[allow(dead_code)]
+pub fn parse_references_lenient(json: &str) -> Result<(Vec<Reference>, Vec<usize>), ParseError> {
+    let values: Vec<Value> = parse_with_context(json)?;
+
+    let mut references = Vec::with_capacity(values.len());
+    let mut failed = Vec::new();
+    for (index, value) in values.into_iter().enumerate() {
+        match serde_json::from_value(value) {
+            Ok(reference) => references.push(reference),
+            Err(_) => failed.push(index),
+        }
+    }
+    Ok((references, failed))
+}
+
+/// 解析响应元数据时各集合的数量上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// 补充链接上限
+    pub max_links: usize,
+    /// 代码引用上限
+    pub max_references: usize,
+    /// 后续提示上限
+    pub max_followups: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_links: 100,
+            max_references: 100,
+            max_followups: 20,
+        }
+    }
+}
+
+/// 解析时被截断的集合
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseTruncation {
+    /// 补充链接是否被截断
+    pub links: bool,
+    /// 代码引用是否被截断
+    pub references: bool,
+    /// 后续提示是否被截断
+    pub followups: bool,
+}
+
+#[allow(dead_code)]
+impl ParseTruncation {
+    /// 是否有任一集合被截断
+    pub fn is_truncated(&self) -> bool {
+        self.links || self.references || self.followups
+    }
+}
+
+/// 按数量上限解析响应元数据
+///
+/// 超出上限的元素只做语法跳过，不会反序列化或分配内存，
+/// 用于防御异常庞大的响应；返回解析结果以及各集合是否被截断。
+/// 字段名与 [`ResponseMetadata`] 的线上字段名一致，重复字段会报错；
+/// 与 `ResponseMetadata` 的反序列化相同，值为 `null` 的字段视为空数组
+#[allow(dead_code)]
+pub fn parse_response_metadata_limited(
+    json: &str,
+    limits: ParseLimits,
+) -> Result<(ResponseMetadata, ParseTruncation), ParseError> {
+    parse_seed_with_context(json, LimitedMetadataSeed { limits })
+}
+
+/// 带数量上限的响应元数据反序列化种子
+struct LimitedMetadataSeed {
+    limits: ParseLimits,
+}
+
+impl<'de> DeserializeSeed<'de> for LimitedMetadataSeed {
+    type Value = (ResponseMetadata, ParseTruncation);
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for LimitedMetadataSeed {
+    type Value = (ResponseMetadata, ParseTruncation);
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("响应元数据对象")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut links = None;
+        let mut references = None;
+        let mut followups = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                SUPPLEMENTARY_WEB_LINKS_KEY => next_capped(
+                    &mut map,
+                    &mut links,
+                    SUPPLEMENTARY_WEB_LINKS_KEY,
+                    self.limits.max_links,
+                )?,
+                REFERENCES_KEY => next_capped(
+                    &mut map,
+                    &mut references,
+                    REFERENCES_KEY,
+                    self.limits.max_references,
+                )?,
+                FOLLOWUP_PROMPTS_KEY => next_capped(
+                    &mut map,
+                    &mut followups,
+                    FOLLOWUP_PROMPTS_KEY,
+                    self.limits.max_followups,
+                )?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        let (links, links_truncated) = links.unwrap_or_default();
+        let (references, references_truncated) = references.unwrap_or_default();
+        let (followups, followups_truncated) = followups.unwrap_or_default();
+        let meta = ResponseMetadata {
+            supplementary_web_links: links,
+            references,
+            followup_prompts: followups,
+        };
+        let truncation = ParseTruncation {
+            links: links_truncated,
+            references: references_truncated,
+            followups: followups_truncated,
+        };
+        Ok((meta, truncation))
+    }
+}
+
+/// 读取一个受限数组字段的值，字段重复出现时报错
+fn next_capped<'de, A: MapAccess<'de>, T: Deserialize<'de>>(
+    map: &mut A,
+    slot: &mut Option<(Vec<T>, bool)>,
+    field: &'static str,
+    cap: usize,
+) -> Result<(), A::Error> {
+    if slot.is_some() {
+        return Err(de::Error::duplicate_field(field));
+    }
+    *slot = Some(map.next_value_seed(CappedSeq::new(cap))?);
+    Ok(())
+}
+
+/// 最多收集 `cap` 个元素的数组反序列化种子
+///
+/// `null` 按空数组处理
+struct CappedSeq<T> {
+    cap: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T> CappedSeq<T> {
+    fn new(cap: usize) -> Self {
+        Self {
+            cap,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for CappedSeq<T> {
+    type Value = (Vec<T>, bool);
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for CappedSeq<T> {
+    type Value = (Vec<T>, bool);
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("数组或 null")
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok((Vec::new(), false))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok((Vec::new(), false))
+    }
+
+    fn visit_some<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+        while items.len() < self.cap {
+            match seq.next_element()? {
+                Some(item) => items.push(item),
+                None => return Ok((items, false)),
+            }
+        }
+        let mut truncated = false;
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            truncated = true;
+        }
+        Ok((items, truncated))
+    }
+}
+
+impl Reference {
+    /// 从已解析的 JSON 值宽松构造引用
+    ///
+    /// 只提取已知字段，类型不匹配的字段直接忽略而不是报错
+    pub fn from_json_value(v: &Value) -> Reference {
+        let string_field = |key: &str| v.get(key).and_then(Value::as_str).map(str::to_string);
+
+        Reference {
+            license_name: string_field("licenseName"),
+            repository: string_field("repository"),
+            url: string_field("url"),
+            information: string_field("information"),
+            recommendation_content_span: typed_field(v, "recommendationContentSpan"),
+            most_relevant_missed_alternative: typed_field(v, "mostRelevantMissedAlternative"),
+        }
+    }
+}
+
+/// 读取并反序列化对象字段，缺失或类型不匹配时返回 `None`
+fn typed_field<T: DeserializeOwned>(v: &Value, key: &str) -> Option<T> {
+    v.get(key)
+        .and_then(|field| serde_json::from_value(field.clone()).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kiro::model::common::span::ContentSpan;
+
+    #[test]
+    fn test_parse_reference() {
+        let reference = parse_reference(r#"{"licenseName":"MIT"}"#).unwrap();
+        assert_eq!(reference.license_name.as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_parse_reference_error_mentions_key() {
+        let json =
+            r#"{"url":"https://example.com","recommendationContentSpan":{"start":"zero","end":5}}"#;
+        let err = parse_reference(json).unwrap_err();
+
+        assert_eq!(err.path, "recommendationContentSpan.start");
+        assert!(err.snippet.contains("zero"));
+        assert!(err.to_string().contains("recommendationContentSpan.start"));
+    }
+
+    #[test]
+    fn test_parse_reference_rejects_trailing_characters() {
+        let json = r#"{"url":"x"} garbage"#;
+        let err = parse_reference(json).unwrap_err();
+
+        assert!(serde_json::from_str::<Reference>(json).is_err());
+        assert_eq!(err.source.classify(), serde_json::error::Category::Syntax);
+        assert_eq!(err.path, ".");
+        assert!(err.snippet.contains("garbage"));
+        assert!(parse_reference("{\"url\":\"x\"}  \n").is_ok());
+    }
+
+    #[test]
+    fn test_parse_references_lenient() {
+        let json = r#"[
+            {"repository": "a/a"},
+            {"recommendationContentSpan": "broken"},
+            {"repository": "c/c"}
+        ]"#;
+        let (references, failed) = parse_references_lenient(json).unwrap();
+
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].repository.as_deref(), Some("a/a"));
+        assert_eq!(references[1].repository.as_deref(), Some("c/c"));
+        assert_eq!(failed, vec![1]);
+    }
+
+    #[test]
+    fn test_parse_references_lenient_not_array() {
+        let err = parse_references_lenient(r#"{"repository":"a/a"}"#).unwrap_err();
+        assert_eq!(err.path, ".");
+        assert_eq!(err.source.classify(), serde_json::error::Category::Data);
+        assert!(err.snippet.contains("repository"));
+
+        let err = parse_references_lenient("[{").unwrap_err();
+        assert_eq!(err.source.classify(), serde_json::error::Category::Eof);
+        assert_eq!(
+            parse_references_lenient("[]").unwrap(),
+            (Vec::new(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_reference_from_json_value_lenient() {
+        let value = serde_json::json!({
+            "repository": "owner/repo",
+            "licenseName": 42,
+            "recommendationContentSpan": {"start": 1, "end": 4},
+            "unexpected": true
+        });
+        let reference = Reference::from_json_value(&value);
+
+        assert_eq!(reference.repository.as_deref(), Some("owner/repo"));
+        assert_eq!(reference.license_name, None);
+        assert_eq!(
+            reference.recommendation_content_span,
+            Some(ContentSpan::new(1, 4))
+        );
+    }
+
+    #[test]
+    fn test_parse_response_metadata_limited() {
+        let json = r#"{
+            "supplementaryWebLinks": [
+                {"url": "https://a.example"},
+                {"url": "https://b.example"},
+                {"url": "https://c.example"}
+            ],
+            "references": [{"repository": "owner/repo"}],
+            "unknownField": {"nested": [1, 2, 3]}
+        }"#;
+        let limits = ParseLimits {
+            max_links: 2,
+            ..ParseLimits::default()
+        };
+        let (meta, truncation) = parse_response_metadata_limited(json, limits).unwrap();
+
+        assert_eq!(meta.supplementary_web_links.len(), 2);
+        assert_eq!(meta.supplementary_web_links[1].url, "https://b.example");
+        assert_eq!(meta.references.len(), 1);
+        assert!(truncation.links);
+        assert!(!truncation.references);
+        assert!(truncation.is_truncated());
+    }
+
+    #[test]
+    fn test_parse_response_metadata_limited_error_path() {
+        let json = r#"{"references": [{"url": 1}]}"#;
+        let err = parse_response_metadata_limited(json, ParseLimits::default()).unwrap_err();
+        assert_eq!(err.path, "references[0].url");
+    }
+
+    #[test]
+    fn test_parse_limited_rejects_duplicate_field() {
+        let json = r#"{"references":[],"references":[]}"#;
+        let err = parse_response_metadata_limited(json, ParseLimits::default()).unwrap_err();
+        assert!(
+            err.source
+                .to_string()
+                .contains("duplicate field `references`")
+        );
+    }
+
+    #[test]
+    fn test_parse_limited_treats_null_as_empty() {
+        let json = r#"{"supplementaryWebLinks":null,"followupPrompts":null}"#;
+        let (meta, truncation) =
+            parse_response_metadata_limited(json, ParseLimits::default()).unwrap();
+        assert_eq!(meta, ResponseMetadata::new());
+        assert!(!truncation.is_truncated());
+
+        // 各入口对 null 的处理一致
+        assert_eq!(
+            serde_json::from_str::<ResponseMetadata>(json).unwrap(),
+            meta
+        );
+        assert_eq!(
+            ResponseMetadata::from_json("null", "[]", "null").unwrap(),
+            meta
+        );
+    }
+
+    #[test]
+    fn test_parse_limited_agrees_with_from_json() {
+        let links = r#"[{"url":"https://example.com","title":"Example"}]"#;
+        let references = r#"[{"licenseName":"MIT","repository":"owner/repo"}]"#;
+        let followups = r#"[{"content":"more","userIntent":"EXPLAIN_CODE_SELECTION"}]"#;
+        let json = format!(
+            r#"{{"{}":{},"{}":{},"{}":{}}}"#,
+            SUPPLEMENTARY_WEB_LINKS_KEY,
+            links,
+            REFERENCES_KEY,
+            references,
+            FOLLOWUP_PROMPTS_KEY,
+            followups
+        );
+        let limits = ParseLimits {
+            max_links: usize::MAX,
+            max_references: usize::MAX,
+            max_followups: usize::MAX,
+        };
+
+        let (limited, truncation) = parse_response_metadata_limited(&json, limits).unwrap();
+        let expected = ResponseMetadata::from_json(links, references, followups).unwrap();
+        assert!(!truncation.is_truncated());
+        assert_eq!(limited.references.len(), 1);
+        assert_eq!(limited, expected);
+        assert_eq!(
+            limited,
+            serde_json::from_str::<ResponseMetadata>(&json).unwrap()
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! 来源标记定义
+//!
+//! 聚合多个响应的元数据时，为每个条目记录其来源响应
+
+use serde::{Deserialize, Serialize};
+
+/// 带来源标记的值
+///
+/// `source` 通常是产生该值的响应 ID
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Provenanced<T> {
+    /// 原始值
+    pub value: T,
+    /// 来源标识
+    pub source: String,
+}
+
+#[allow(dead_code)]
+impl<T> Provenanced<T> {
+    /// 创建带来源标记的值
+    pub fn new(value: T, source: impl Into<String>) -> Self {
+        Self {
+            value,
+            source: source.into(),
+        }
+    }
+
+    /// 判断是否来自指定来源
+    pub fn is_from(&self, source: &str) -> bool {
+        self.source == source
+    }
+
+    /// 去除来源标记，取出原始值
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// 为一组值附加相同的来源标记
+#[allow(dead_code)]
+pub fn attach_source<T>(values: Vec<T>, source: &str) -> Vec<Provenanced<T>> {
+    values
+        .into_iter()
+        .map(|value| Provenanced::new(value, source))
+        .collect()
+}
+
+/// 去除一组值的来源标记
+#[allow(dead_code)]
+pub fn strip_sources<T>(values: Vec<Provenanced<T>>) -> Vec<T> {
+    values.into_iter().map(Provenanced::into_inner).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kiro::model::common::reference::Reference;
+
+    #[test]
+    fn test_filter_references_by_source() {
+        let mut tagged = attach_source(
+            vec![
+                Reference::new().with_repository("a/a"),
+                Reference::new().with_repository("b/b"),
+            ],
+            "resp-1",
+        );
+        tagged.push(Provenanced::new(
+            Reference::new().with_repository("c/c"),
+            "resp-2",
+        ));
+
+        let from_first: Vec<Reference> = strip_sources(
+            tagged
+                .into_iter()
+                .filter(|item| item.is_from("resp-1"))
+                .collect(),
+        );
+
+        assert_eq!(from_first.len(), 2);
+        assert_eq!(from_first[0].repository.as_deref(), Some("a/a"));
+        assert_eq!(from_first[1].repository.as_deref(), Some("b/b"));
+    }
+}
@@ -0,0 +1,271 @@
+//! 代码查询定义
+
+use serde::{Deserialize, Serialize};
+
+use super::error::ModelError;
+use super::language::ProgrammingLanguage;
+use super::util::{trim_in_place, trim_optional};
+use super::validate::{Validate, serialize_validated};
+
+/// 代码查询
+///
+/// 关联一次代码查询与其语言、触发消息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "non-exhaustive", non_exhaustive)]
+pub struct CodeQuery {
+    /// 代码查询 ID（兼容后端偶尔返回的 `codeQueryID`）
+    #[serde(alias = "codeQueryID")]
+    pub code_query_id: String,
+    /// 编程语言
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub programming_language: Option<ProgrammingLanguage>,
+    /// 用户输入消息 ID（兼容后端偶尔返回的 `userInputMessageID`）
+    #[serde(alias = "userInputMessageID", skip_serializing_if = "Option::is_none")]
+    pub user_input_message_id: Option<String>,
+}
+
+#[allow(dead_code)]
+impl CodeQuery {
+    /// 创建新的代码查询
+    pub fn new(code_query_id: impl Into<String>) -> Self {
+        Self {
+            code_query_id: code_query_id.into(),
+            programming_language: None,
+            user_input_message_id: None,
+        }
+    }
+
+    /// 根据文件路径创建代码查询
+    ///
+    /// 语言按扩展名推断（见 [`ProgrammingLanguage::from_path`]），无法推断时不设置语言
+    pub fn for_path(code_query_id: impl Into<String>, path: impl AsRef<std::path::Path>) -> Self {
+        Self {
+            programming_language: ProgrammingLanguage::from_path(path.as_ref()),
+            ..Self::new(code_query_id)
+        }
+    }
+
+    /// 设置编程语言
+    pub fn with_programming_language(mut self, language: ProgrammingLanguage) -> Self {
+        self.programming_language = Some(language);
+        self
+    }
+
+    /// 设置用户输入消息 ID
+    pub fn with_user_input_message_id(mut self, id: impl Into<String>) -> Self {
+        self.user_input_message_id = Some(id.into());
+        self
+    }
+
+    /// 校验后序列化为请求 JSON
+    ///
+    /// 校验失败时返回错误，不会产生 JSON
+    pub fn to_request_json(&self) -> Result<String, ModelError> {
+        serialize_validated(self)
+    }
+
+    /// 展开为查询参数键值对
+    ///
+    /// 键使用线上字段名，语言取语言名称；未设置的字段不输出。
+    /// 值未经编码，由调用方按需进行 URL 编码
+    pub fn to_query_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![("codeQueryId".to_string(), self.code_query_id.clone())];
+        if let Some(language) = &self.programming_language {
+            params.push((
+                "programmingLanguage".to_string(),
+                language.language_name.to_string(),
+            ));
+        }
+        if let Some(id) = &self.user_input_message_id {
+            params.push(("userInputMessageId".to_string(), id.clone()));
+        }
+        params
+    }
+
+    /// 规范化字符串字段
+    ///
+    /// 去除首尾空白，可选字段去除后为空则置为 `None`；语言名称为空时移除语言
+    pub fn normalize(&mut self) {
+        trim_in_place(&mut self.code_query_id);
+        trim_optional(&mut self.user_input_message_id);
+        if let Some(language) = &mut self.programming_language {
+            language.normalize();
+        }
+        if self
+            .programming_language
+            .as_ref()
+            .is_some_and(|language| language.language_name.is_empty())
+        {
+            self.programming_language = None;
+        }
+    }
+}
+
+impl Validate for CodeQuery {
+    /// 校验代码查询
+    ///
+    /// 查询 ID 不能为空；设置了语言时语言名称不能为空
+    fn validate(&self) -> Result<(), ModelError> {
+        if self.code_query_id.trim().is_empty() {
+            return Err(ModelError::EmptyField("codeQueryId"));
+        }
+        if self
+            .programming_language
+            .as_ref()
+            .is_some_and(|language| language.language_name.trim().is_empty())
+        {
+            return Err(ModelError::EmptyField("programmingLanguage.languageName"));
+        }
+        Ok(())
+    }
+}
+
+/// 代码查询的遥测摘要
+///
+/// 仅记录可选字段是否存在，不携带任何 ID，可安全写入日志
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct CodeQueryTelemetry {
+    /// 是否设置了编程语言
+    pub has_language: bool,
+    /// 是否设置了用户输入消息 ID
+    pub has_message_id: bool,
+}
+
+impl From<&CodeQuery> for CodeQueryTelemetry {
+    fn from(query: &CodeQuery) -> Self {
+        Self {
+            has_language: query.programming_language.is_some(),
+            has_message_id: query.user_input_message_id.is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_sets_all_fields() {
+        let query = CodeQuery::new("q-1")
+            .with_programming_language(ProgrammingLanguage::RUST)
+            .with_user_input_message_id("m-1");
+
+        assert_eq!(query.code_query_id, "q-1");
+        assert_eq!(query.programming_language, Some(ProgrammingLanguage::RUST));
+        assert_eq!(query.user_input_message_id.as_deref(), Some("m-1"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(CodeQuery::new("q-1").validate().is_ok());
+        assert_eq!(
+            CodeQuery::new(" ").validate(),
+            Err(ModelError::EmptyField("codeQueryId"))
+        );
+        assert_eq!(
+            CodeQuery::new("q-1")
+                .with_programming_language(ProgrammingLanguage::new(""))
+                .validate(),
+            Err(ModelError::EmptyField("programmingLanguage.languageName"))
+        );
+    }
+
+    #[test]
+    fn test_to_request_json() {
+        let query = CodeQuery::new("q-1").with_programming_language(ProgrammingLanguage::RUST);
+        assert_eq!(
+            query.to_request_json().unwrap(),
+            r#"{"codeQueryId":"q-1","programmingLanguage":{"languageName":"rust"}}"#
+        );
+    }
+
+    #[test]
+    fn test_to_request_json_invalid() {
+        assert_eq!(
+            CodeQuery::new("").to_request_json(),
+            Err(ModelError::EmptyField("codeQueryId"))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_camel_case_id() {
+        let json = r#"{"codeQueryId":"q-1","userInputMessageId":"m-1"}"#;
+        let query: CodeQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.code_query_id, "q-1");
+        assert_eq!(query.user_input_message_id.as_deref(), Some("m-1"));
+    }
+
+    #[test]
+    fn test_deserialize_uppercase_id() {
+        let json = r#"{"codeQueryID":"q-2","userInputMessageID":"m-2"}"#;
+        let query: CodeQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.code_query_id, "q-2");
+        assert_eq!(query.user_input_message_id.as_deref(), Some("m-2"));
+    }
+
+    #[test]
+    fn test_serialize_uses_camel_case_id() {
+        let query = CodeQuery::new("q-3").with_user_input_message_id("m-3");
+        let json = serde_json::to_string(&query).unwrap();
+        assert_eq!(json, r#"{"codeQueryId":"q-3","userInputMessageId":"m-3"}"#);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut query = CodeQuery::new(" q-1 ")
+            .with_programming_language(ProgrammingLanguage::new(" rust "))
+            .with_user_input_message_id("  ");
+        query.normalize();
+
+        assert_eq!(query.code_query_id, "q-1");
+        assert_eq!(query.programming_language, Some(ProgrammingLanguage::RUST));
+        assert_eq!(query.user_input_message_id, None);
+    }
+
+    #[test]
+    fn test_telemetry() {
+        let query = CodeQuery::new("secret-id").with_programming_language(ProgrammingLanguage::GO);
+        let telemetry = CodeQueryTelemetry::from(&query);
+
+        assert_eq!(
+            telemetry,
+            CodeQueryTelemetry {
+                has_language: true,
+                has_message_id: false,
+            }
+        );
+        assert!(
+            !serde_json::to_string(&telemetry)
+                .unwrap()
+                .contains("secret-id")
+        );
+    }
+
+    #[test]
+    fn test_to_query_params() {
+        let query = CodeQuery::new("q-1").with_programming_language(ProgrammingLanguage::RUST);
+
+        assert_eq!(
+            query.to_query_params(),
+            vec![
+                ("codeQueryId".to_string(), "q-1".to_string()),
+                ("programmingLanguage".to_string(), "rust".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_for_path() {
+        let query = CodeQuery::for_path("q1", "main.rs");
+
+        assert_eq!(query.code_query_id, "q1");
+        assert_eq!(query.programming_language, Some(ProgrammingLanguage::RUST));
+        assert_eq!(
+            CodeQuery::for_path("q2", "README").programming_language,
+            None
+        );
+    }
+}
@@ -0,0 +1,900 @@
+//! 代码引用定义
+//!
+//! 定义响应中附带的代码引用（许可证、仓库、来源链接等）
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::error::ModelError;
+use super::license::{find_spdx_license, normalize_spdx_expression};
+use super::span::ContentSpan;
+use super::util::{canonicalize_url, trim_in_place, trim_optional};
+use super::validate::Validate;
+
+/// 最相关的错过的替代方案
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MostRelevantMissedAlternative {
+    /// 来源链接
+    pub url: String,
+    /// 许可证名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_name: Option<String>,
+    /// 仓库名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+}
+
+impl MostRelevantMissedAlternative {
+    /// 将引用提升为替代方案
+    ///
+    /// 映射 url、许可证和仓库；引用没有 url 时返回 `None`
+    pub fn from_reference(reference: &Reference) -> Option<Self> {
+        Some(Self {
+            url: reference.url.clone()?,
+            license_name: reference.license_name.clone(),
+            repository: reference.repository.clone(),
+        })
+    }
+
+    /// 规范化字符串字段
+    ///
+    /// 去除首尾空白，可选字段去除后为空则置为 `None`
+    pub fn normalize(&mut self) {
+        trim_in_place(&mut self.url);
+        trim_optional(&mut self.license_name);
+        trim_optional(&mut self.repository);
+    }
+}
+
+/// 代码引用
+///
+/// 标记响应内容中来源于外部代码的部分
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "non-exhaustive", non_exhaustive)]
+pub struct Reference {
+    /// 许可证名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_name: Option<String>,
+    /// 仓库名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// 来源链接
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// 附加信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub information: Option<String>,
+    /// 引用在响应内容中的范围
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommendation_content_span: Option<ContentSpan>,
+    /// 最相关的错过的替代方案
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub most_relevant_missed_alternative: Option<MostRelevantMissedAlternative>,
+}
+
+impl Reference {
+    /// 创建空引用
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建带校验的构建器
+    pub fn builder() -> ReferenceBuilder {
+        ReferenceBuilder::default()
+    }
+
+    /// 设置许可证名称
+    pub fn with_license_name(mut self, license_name: impl Into<String>) -> Self {
+        self.license_name = Some(license_name.into());
+        self
+    }
+
+    /// 设置经过校验的 SPDX 许可证标识或表达式
+    ///
+    /// 接受由已知标识组成的 SPDX 表达式（如 `MIT OR Apache-2.0`），存储为规范写法；
+    /// 语法不合法或包含不在已知列表中的标识时返回错误，见 [`normalize_spdx_expression`]
+    pub fn with_spdx_license(mut self, expression: &str) -> Result<Self, ModelError> {
+        let license = normalize_spdx_expression(expression)
+            .ok_or_else(|| ModelError::InvalidLicenseExpression(expression.to_string()))?;
+        self.license_name = Some(license);
+        Ok(self)
+    }
+
+    /// 设置仓库名称
+    pub fn with_repository(mut self, repository: impl Into<String>) -> Self {
+        self.repository = Some(repository.into());
+        self
+    }
+
+    /// 设置来源链接
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// 设置附加信息
+    pub fn with_information(mut self, information: impl Into<String>) -> Self {
+        self.information = Some(information.into());
+        self
+    }
+
+    /// 设置内容范围
+    pub fn with_span(mut self, span: ContentSpan) -> Self {
+        self.recommendation_content_span = Some(span);
+        self
+    }
+
+    /// 以区间形式设置内容范围
+    pub fn with_span_range(self, range: Range<i32>) -> Self {
+        self.with_span(ContentSpan::from(range))
+    }
+
+    /// 设置最相关的错过的替代方案
+    pub fn with_most_relevant_missed_alternative(
+        mut self,
+        alternative: MostRelevantMissedAlternative,
+    ) -> Self {
+        self.most_relevant_missed_alternative = Some(alternative);
+        self
+    }
+
+    /// 获取许可证名称
+    pub fn license(&self) -> Option<&str> {
+        self.license_name.as_deref()
+    }
+
+    /// 获取仓库名称
+    pub fn repo(&self) -> Option<&str> {
+        self.repository.as_deref()
+    }
+
+    /// 获取来源链接
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// 获取附加信息
+    pub fn info(&self) -> Option<&str> {
+        self.information.as_deref()
+    }
+
+    /// 判断是否包含任一来源信息（url / repository / information）
+    pub fn has_source(&self) -> bool {
+        self.url.is_some() || self.repository.is_some() || self.information.is_some()
+    }
+
+    /// 计算引用的稳定内容 ID
+    ///
+    /// 基于许可证、仓库、链接和附加信息（不含内容范围）计算 SHA-256，
+    /// 取前 8 字节作为 ID，跨进程和版本保持一致，可用作缓存键
+    pub fn content_id(&self) -> u64 {
+        let mut hasher = Sha256::new();
+        for field in [
+            &self.license_name,
+            &self.repository,
+            &self.url,
+            &self.information,
+        ] {
+            match field {
+                Some(value) => {
+                    hasher.update([1u8]);
+                    hasher.update((value.len() as u64).to_be_bytes());
+                    hasher.update(value.as_bytes());
+                }
+                None => hasher.update([0u8]),
+            }
+        }
+        let digest = hasher.finalize();
+        let mut id = [0u8; 8];
+        id.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(id)
+    }
+
+    /// 获取规范化的来源链接（主机名小写、去除末尾斜杠）
+    pub fn canonical_url(&self) -> Option<String> {
+        self.url.as_deref().map(canonicalize_url)
+    }
+
+    /// 判断两个引用是否指向同一来源
+    ///
+    /// 比较规范化后的来源链接，忽略主机名大小写和末尾斜杠；任一方没有链接时返回 `false`
+    pub fn same_source(&self, other: &Reference) -> bool {
+        match (self.canonical_url(), other.canonical_url()) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// 生成单行紧凑描述，仅包含已设置的字段
+    ///
+    /// 用于批量记录日志，比 `Debug` 输出更简短
+    pub fn compact(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(license_name) = &self.license_name {
+            parts.push(format!("license={}", license_name));
+        }
+        if let Some(repository) = &self.repository {
+            parts.push(format!("repo={}", repository));
+        }
+        if let Some(url) = &self.url {
+            parts.push(format!("url={}", url));
+        }
+        if let Some(information) = &self.information {
+            parts.push(format!("info={}", information));
+        }
+        if let Some(span) = &self.recommendation_content_span {
+            parts.push(format!("span={}..{}", span.start, span.end));
+        }
+        if let Some(alternative) = &self.most_relevant_missed_alternative {
+            parts.push(format!("alt={}", alternative.url));
+        }
+        format!("Reference({})", parts.join(", "))
+    }
+
+    /// 规范化字符串字段
+    ///
+    /// 去除首尾空白，可选字段去除后为空则置为 `None`
+    pub fn normalize(&mut self) {
+        trim_optional(&mut self.license_name);
+        trim_optional(&mut self.repository);
+        trim_optional(&mut self.url);
+        trim_optional(&mut self.information);
+        if let Some(alternative) = &mut self.most_relevant_missed_alternative {
+            alternative.normalize();
+        }
+    }
+}
+
+impl Validate for Reference {
+    /// 校验引用
+    ///
+    /// 设置了内容范围但没有任何来源信息的引用无法定位出处，视为无效
+    fn validate(&self) -> Result<(), ModelError> {
+        if self.recommendation_content_span.is_some() && !self.has_source() {
+            return Err(ModelError::SpanWithoutSource);
+        }
+        Ok(())
+    }
+}
+
+/// 代码引用构建器
+///
+/// 与 `with_*` 方法不同，[`ReferenceBuilder::build`] 会在构建时执行校验
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceBuilder {
+    reference: Reference,
+}
+
+impl ReferenceBuilder {
+    /// 设置许可证名称
+    pub fn license(mut self, license_name: impl Into<String>) -> Self {
+        self.reference.license_name = Some(license_name.into());
+        self
+    }
+
+    /// 设置仓库名称
+    pub fn repository(mut self, repository: impl Into<String>) -> Self {
+        self.reference.repository = Some(repository.into());
+        self
+    }
+
+    /// 设置来源链接
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.reference.url = Some(url.into());
+        self
+    }
+
+    /// 设置附加信息
+    pub fn information(mut self, information: impl Into<String>) -> Self {
+        self.reference.information = Some(information.into());
+        self
+    }
+
+    /// 设置内容范围
+    pub fn span(mut self, span: impl Into<ContentSpan>) -> Self {
+        self.reference.recommendation_content_span = Some(span.into());
+        self
+    }
+
+    /// 设置最相关的错过的替代方案
+    pub fn most_relevant_missed_alternative(
+        mut self,
+        alternative: MostRelevantMissedAlternative,
+    ) -> Self {
+        self.reference.most_relevant_missed_alternative = Some(alternative);
+        self
+    }
+
+    /// 校验并构建引用
+    pub fn build(self) -> Result<Reference, ModelError> {
+        self.reference.validate()?;
+        Ok(self.reference)
+    }
+}
+
+/// 按仓库分组引用
+///
+/// 没有仓库信息的引用归入空字符串键下
+#[allow(dead_code)]
+pub fn group_references_by_repo(refs: &[Reference]) -> HashMap<String, Vec<&Reference>> {
+    let mut groups: HashMap<String, Vec<&Reference>> = HashMap::new();
+    for reference in refs {
+        let key = reference.repository.clone().unwrap_or_default();
+        groups.entry(key).or_default().push(reference);
+    }
+    groups
+}
+
+/// 获取出现次数最多的许可证
+///
+/// 次数相同时按字母顺序取最小者；没有任何许可证时返回 `None`
+#[allow(dead_code)]
+pub fn dominant_license(refs: &[Reference]) -> Option<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for license_name in refs.iter().filter_map(|r| r.license_name.as_deref()) {
+        *counts.entry(license_name).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(a_name, a_count), (b_name, b_count)| {
+            a_count.cmp(b_count).then_with(|| b_name.cmp(a_name))
+        })
+        .map(|(name, _)| name.to_string())
+}
+
+/// 统计引用涉及的不同仓库数量（忽略没有仓库信息的引用）
+#[allow(dead_code)]
+pub fn distinct_repositories(refs: &[Reference]) -> usize {
+    refs.iter()
+        .filter_map(|reference| reference.repository.as_deref())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// 按内容范围排序后序列化引用数组
+///
+/// 按范围起始、结束位置排序，没有范围的引用排在最后；
+/// 排序键相同时按序列化结果排序，保证输出与输入顺序无关
+#[allow(dead_code)]
+pub fn serialize_references_sorted(refs: &[Reference]) -> String {
+    let mut entries: Vec<(Option<(i32, i32)>, serde_json::Value)> = refs
+        .iter()
+        .map(|reference| {
+            let key = reference
+                .recommendation_content_span
+                .map(|span| (span.start, span.end));
+            // Reference 只含字符串和整数字段，没有映射键或自定义序列化，不会失败
+            let value = serde_json::to_value(reference).expect("Reference 序列化不会失败");
+            (key, value)
+        })
+        .collect();
+    entries.sort_by(|(a_key, a_value), (b_key, b_value)| {
+        // 没有范围的引用（is_none 为 true）排在最后
+        (a_key.is_none(), a_key)
+            .cmp(&(b_key.is_none(), b_key))
+            .then_with(|| a_value.to_string().cmp(&b_value.to_string()))
+    });
+    let values: Vec<serde_json::Value> = entries.into_iter().map(|(_, value)| value).collect();
+    serde_json::Value::Array(values).to_string()
+}
+
+/// 按是否带有许可证拆分引用
+///
+/// 返回 `(有许可证, 无许可证)`，各自保持原有顺序
+#[allow(dead_code)]
+pub fn partition_licensed(refs: Vec<Reference>) -> (Vec<Reference>, Vec<Reference>) {
+    refs.into_iter()
+        .partition(|reference| reference.license_name.is_some())
+}
+
+/// 选出信息最完整、最权威的引用
+///
+/// 评分规则：有链接 +4，有许可证 +4（许可证为已知 SPDX 标识再 +1），
+/// 有仓库名 +2，有附加信息 +1；分数相同时取最先出现的引用，列表为空时返回 `None`
+#[allow(dead_code)]
+pub fn best_reference(refs: &[Reference]) -> Option<&Reference> {
+    fn authority(reference: &Reference) -> u8 {
+        let mut score = 0;
+        if reference.url.is_some() {
+            score += 4;
+        }
+        if let Some(license) = reference.license_name.as_deref() {
+            score += 4;
+            if find_spdx_license(license).is_some() {
+                score += 1;
+            }
+        }
+        if reference.repository.is_some() {
+            score += 2;
+        }
+        if reference.information.is_some() {
+            score += 1;
+        }
+        score
+    }
+
+    refs.iter()
+        .rev()
+        .max_by_key(|reference| authority(reference))
+}
+
+/// 合并相邻且来源相同的连续引用
+///
+/// 相邻两个引用的仓库相同（或 [`Reference::same_source`] 成立），
+/// 且后者的内容范围紧接或重叠前者时，合并为一个范围取并集的引用，
+/// 其余字段保留前者的值
+#[allow(dead_code)]
+pub fn coalesce_adjacent(refs: Vec<Reference>) -> Vec<Reference> {
+    let mut coalesced: Vec<Reference> = Vec::with_capacity(refs.len());
+    for reference in refs {
+        if let Some(last) = coalesced.last_mut() {
+            let same_repo = last.repository.is_some() && last.repository == reference.repository;
+            if (same_repo || last.same_source(&reference))
+                && let (Some(prev), Some(next)) = (
+                    last.recommendation_content_span,
+                    reference.recommendation_content_span,
+                )
+                && next.start <= prev.end
+                && prev.start <= next.end
+            {
+                last.recommendation_content_span = Some(ContentSpan::new(
+                    prev.start.min(next.start),
+                    prev.end.max(next.end),
+                ));
+                continue;
+            }
+        }
+        coalesced.push(reference);
+    }
+    coalesced
+}
+
+/// 计算引用链接的最长公共前缀（按路径分段对齐）
+///
+/// 只在 `/` 边界处截断，结果至少包含协议和主机部分；
+/// 没有链接或主机不一致时返回 `None`
+#[allow(dead_code)]
+pub fn common_url_prefix(refs: &[Reference]) -> Option<String> {
+    let mut urls = refs.iter().filter_map(|reference| reference.url.as_deref());
+    let mut prefix: Vec<&str> = urls.next()?.split('/').collect();
+    for url in urls {
+        let common = prefix
+            .iter()
+            .zip(url.split('/'))
+            .take_while(|(a, b)| *a == b)
+            .count();
+        prefix.truncate(common);
+    }
+    // "https:" / "" / host 三段构成最短的有效前缀
+    if prefix.len() < 3 || prefix[2].is_empty() {
+        return None;
+    }
+    Some(prefix.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_span_without_source() {
+        let reference = Reference::new()
+            .with_license_name("MIT")
+            .with_span(ContentSpan::new(0, 10));
+        assert_eq!(reference.validate(), Err(ModelError::SpanWithoutSource));
+    }
+
+    #[test]
+    fn test_validate_span_with_source() {
+        let reference = Reference::new()
+            .with_repository("rust-lang/rust")
+            .with_span(ContentSpan::new(0, 10));
+        assert!(reference.validate().is_ok());
+    }
+
+    #[test]
+    fn test_builder_sets_all_fields() {
+        let alternative = MostRelevantMissedAlternative {
+            url: "https://alt.example".to_string(),
+            license_name: None,
+            repository: None,
+        };
+        let reference = Reference::new()
+            .with_license_name("MIT")
+            .with_repository("owner/repo")
+            .with_url("https://example.com")
+            .with_information("info")
+            .with_span(ContentSpan::new(1, 2))
+            .with_most_relevant_missed_alternative(alternative.clone());
+
+        assert_eq!(reference.license_name.as_deref(), Some("MIT"));
+        assert_eq!(reference.repository.as_deref(), Some("owner/repo"));
+        assert_eq!(reference.url.as_deref(), Some("https://example.com"));
+        assert_eq!(reference.information.as_deref(), Some("info"));
+        assert_eq!(
+            reference.recommendation_content_span,
+            Some(ContentSpan::new(1, 2))
+        );
+        assert_eq!(
+            reference.most_relevant_missed_alternative,
+            Some(alternative)
+        );
+    }
+
+    #[test]
+    fn test_with_span_range() {
+        let reference = Reference::new().with_span_range(5..10);
+        let span = reference.recommendation_content_span.unwrap();
+
+        assert_eq!(span, ContentSpan::new(5, 10));
+        assert_eq!(span.len(), 5);
+    }
+
+    #[test]
+    fn test_accessors() {
+        let reference = Reference::new()
+            .with_license_name("MIT")
+            .with_url("https://example.com");
+
+        assert_eq!(reference.license(), Some("MIT"));
+        assert_eq!(reference.repo(), None);
+        assert_eq!(reference.url(), Some("https://example.com"));
+        assert_eq!(reference.info(), None);
+    }
+
+    #[test]
+    fn test_compact() {
+        assert_eq!(Reference::new().compact(), "Reference()");
+
+        let reference = Reference::new()
+            .with_license_name("MIT")
+            .with_repository("owner/repo")
+            .with_url("https://example.com")
+            .with_information("info")
+            .with_span_range(0..5)
+            .with_most_relevant_missed_alternative(MostRelevantMissedAlternative {
+                url: "https://alt.example".to_string(),
+                license_name: None,
+                repository: None,
+            });
+        assert_eq!(
+            reference.compact(),
+            "Reference(license=MIT, repo=owner/repo, url=https://example.com, info=info, span=0..5, alt=https://alt.example)"
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let json = r#"{
+            "licenseName": "MIT",
+            "repository": "rust-lang/rust",
+            "url": "https://github.com/rust-lang/rust",
+            "recommendationContentSpan": {"start": 0, "end": 10}
+        }"#;
+        let reference: Reference = serde_json::from_str(json).unwrap();
+        assert_eq!(reference.license_name.as_deref(), Some("MIT"));
+        assert_eq!(
+            reference.recommendation_content_span,
+            Some(ContentSpan::new(0, 10))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_span_with_extra_keys() {
+        let json = r#"{
+            "url": "https://example.com",
+            "recommendationContentSpan": {"start": 2, "end": 8, "kind": "content"}
+        }"#;
+        let reference: Reference = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            reference.recommendation_content_span,
+            Some(ContentSpan::new(2, 8))
+        );
+    }
+
+    #[test]
+    fn test_group_references_by_repo() {
+        let refs = vec![
+            Reference::new()
+                .with_repository("a/a")
+                .with_url("https://x"),
+            Reference::new().with_url("https://y"),
+            Reference::new().with_repository("b/b"),
+            Reference::new()
+                .with_repository("a/a")
+                .with_url("https://z"),
+        ];
+
+        let groups = group_references_by_repo(&refs);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups["a/a"].len(), 2);
+        assert_eq!(groups["a/a"][1].url.as_deref(), Some("https://z"));
+        assert_eq!(groups["b/b"].len(), 1);
+        assert_eq!(groups[""][0].url.as_deref(), Some("https://y"));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut reference = Reference::new()
+            .with_url(" https://example.com ")
+            .with_repository("   ")
+            .with_license_name("MIT\n");
+        reference.normalize();
+
+        assert_eq!(reference.url.as_deref(), Some("https://example.com"));
+        assert_eq!(reference.repository, None);
+        assert_eq!(reference.license_name.as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_dominant_license() {
+        let refs = vec![
+            Reference::new().with_license_name("MIT"),
+            Reference::new().with_license_name("Apache-2.0"),
+            Reference::new(),
+            Reference::new().with_license_name("MIT"),
+        ];
+        assert_eq!(dominant_license(&refs).as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_dominant_license_tie_breaks_alphabetically() {
+        let refs = vec![
+            Reference::new().with_license_name("MIT"),
+            Reference::new().with_license_name("Apache-2.0"),
+        ];
+        assert_eq!(dominant_license(&refs).as_deref(), Some("Apache-2.0"));
+        assert_eq!(dominant_license(&[]), None);
+    }
+
+    #[test]
+    fn test_same_source() {
+        let a = Reference::new().with_url("https://GitHub.com/owner/repo/");
+        let b = Reference::new().with_url("https://github.com/owner/repo");
+        let c = Reference::new().with_url("https://github.com/owner/other");
+
+        assert_eq!(
+            a.canonical_url().as_deref(),
+            Some("https://github.com/owner/repo")
+        );
+        assert!(a.same_source(&b));
+        assert!(!a.same_source(&c));
+        assert!(!Reference::new().same_source(&Reference::new()));
+    }
+
+    #[test]
+    fn test_alternative_from_reference() {
+        let reference = Reference::new()
+            .with_url("https://github.com/owner/repo")
+            .with_license_name("MIT")
+            .with_repository("owner/repo")
+            .with_information("ignored");
+
+        assert_eq!(
+            MostRelevantMissedAlternative::from_reference(&reference),
+            Some(MostRelevantMissedAlternative {
+                url: "https://github.com/owner/repo".to_string(),
+                license_name: Some("MIT".to_string()),
+                repository: Some("owner/repo".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_alternative_from_reference_without_url() {
+        let reference = Reference::new().with_repository("owner/repo");
+        assert_eq!(
+            MostRelevantMissedAlternative::from_reference(&reference),
+            None
+        );
+    }
+
+    #[test]
+    fn test_content_id() {
+        let base = Reference::new()
+            .with_repository("owner/repo")
+            .with_url("https://github.com/owner/repo");
+        let other_span = base.clone().with_span_range(0..5);
+        let other_repo = base.clone().with_repository("owner/other");
+
+        assert_eq!(base.content_id(), other_span.content_id());
+        assert_ne!(base.content_id(), other_repo.content_id());
+        // 字段边界不同的引用不应产生相同 ID
+        let a = Reference::new().with_repository("ab").with_url("c");
+        let b = Reference::new().with_repository("a").with_url("bc");
+        assert_ne!(a.content_id(), b.content_id());
+    }
+
+    #[test]
+    fn test_partition_licensed() {
+        let refs = vec![
+            Reference::new().with_url("a").with_license_name("MIT"),
+            Reference::new().with_url("b"),
+            Reference::new()
+                .with_url("c")
+                .with_license_name("Apache-2.0"),
+        ];
+
+        let (licensed, unlicensed) = partition_licensed(refs);
+
+        let urls = |refs: &[Reference]| -> Vec<String> {
+            refs.iter().filter_map(|r| r.url.clone()).collect()
+        };
+        assert_eq!(urls(&licensed), vec!["a", "c"]);
+        assert_eq!(urls(&unlicensed), vec!["b"]);
+    }
+
+    #[test]
+    fn test_builder_build() -> Result<(), ModelError> {
+        let reference = Reference::builder()
+            .url("https://github.com/owner/repo")
+            .license("MIT")
+            .span(0..5)
+            .build()?;
+
+        assert_eq!(reference.url(), Some("https://github.com/owner/repo"));
+        assert_eq!(reference.license(), Some("MIT"));
+        assert_eq!(
+            reference.recommendation_content_span,
+            Some(ContentSpan::new(0, 5))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_build_invalid() {
+        let result = Reference::builder().license("MIT").span(0..5).build();
+        assert_eq!(result, Err(ModelError::SpanWithoutSource));
+    }
+
+    #[test]
+    fn test_common_url_prefix() {
+        let refs = vec![
+            Reference::new().with_url("https://docs.rs/serde/1.0/serde/trait.Serialize.html"),
+            Reference::new().with_url("https://docs.rs/serde/1.0/serde/de/index.html"),
+            Reference::new(),
+            Reference::new().with_url("https://docs.rs/serde/1.0/serde_json"),
+        ];
+        assert_eq!(
+            common_url_prefix(&refs).as_deref(),
+            Some("https://docs.rs/serde/1.0")
+        );
+    }
+
+    #[test]
+    fn test_common_url_prefix_different_hosts() {
+        let refs = vec![
+            Reference::new().with_url("https://a.example/x"),
+            Reference::new().with_url("https://b.example/x"),
+        ];
+        assert_eq!(common_url_prefix(&refs), None);
+        assert_eq!(common_url_prefix(&[]), None);
+    }
+
+    #[test]
+    fn test_distinct_repositories() {
+        let refs = vec![
+            Reference::new().with_repository("o/a"),
+            Reference::new().with_repository("o/b"),
+            Reference::new().with_repository("o/a"),
+            Reference::new().with_url("https://x"),
+        ];
+        assert_eq!(distinct_repositories(&refs), 2);
+        assert_eq!(distinct_repositories(&[]), 0);
+    }
+
+    #[test]
+    fn test_with_spdx_license() {
+        let reference = Reference::new().with_spdx_license("MIT").unwrap();
+        assert_eq!(reference.license(), Some("MIT"));
+
+        let reference = Reference::new().with_spdx_license("apache-2.0").unwrap();
+        assert_eq!(reference.license(), Some("Apache-2.0"));
+
+        let reference = Reference::new()
+            .with_spdx_license("mit OR Apache-2.0")
+            .unwrap();
+        assert_eq!(reference.license(), Some("MIT OR Apache-2.0"));
+
+        assert_eq!(
+            Reference::new().with_spdx_license("NotALicense"),
+            Err(ModelError::InvalidLicenseExpression(
+                "NotALicense".to_string()
+            ))
+        );
+        assert_eq!(
+            Reference::new().with_spdx_license("MIT OR"),
+            Err(ModelError::InvalidLicenseExpression("MIT OR".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_serialize_references_sorted() {
+        let a = Reference::new()
+            .with_url("https://a")
+            .with_span_range(10..20);
+        let b = Reference::new().with_url("https://b").with_span_range(0..5);
+        let c = Reference::new().with_url("https://c");
+        let d = Reference::new().with_url("https://d");
+
+        let first = serialize_references_sorted(&[a.clone(), b.clone(), c.clone(), d.clone()]);
+        let second = serialize_references_sorted(&[d, c, a, b]);
+
+        assert_eq!(first, second);
+        let urls: Vec<String> = serde_json::from_str::<Vec<Reference>>(&first)
+            .unwrap()
+            .into_iter()
+            .filter_map(|reference| reference.url)
+            .collect();
+        assert_eq!(
+            urls,
+            vec!["https://b", "https://a", "https://c", "https://d"]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_adjacent() {
+        let refs = vec![
+            Reference::new()
+                .with_repository("a/a")
+                .with_span_range(0..10),
+            Reference::new()
+                .with_repository("a/a")
+                .with_span_range(10..20),
+            Reference::new()
+                .with_repository("b/b")
+                .with_span_range(20..30),
+            Reference::new()
+                .with_repository("a/a")
+                .with_span_range(40..50),
+        ];
+        let coalesced = coalesce_adjacent(refs);
+
+        assert_eq!(coalesced.len(), 3);
+        assert_eq!(
+            coalesced[0].recommendation_content_span,
+            Some(ContentSpan::new(0, 20))
+        );
+        assert_eq!(coalesced[1].repository.as_deref(), Some("b/b"));
+        assert_eq!(
+            coalesced[2].recommendation_content_span,
+            Some(ContentSpan::new(40, 50))
+        );
+    }
+
+    #[test]
+    fn test_best_reference() {
+        let refs = vec![
+            Reference::new().with_repository("bare/repo"),
+            Reference::new()
+                .with_url("https://github.com/full/repo")
+                .with_license_name("MIT")
+                .with_repository("full/repo"),
+            Reference::new().with_url("https://example.com"),
+        ];
+
+        assert_eq!(
+            best_reference(&refs).and_then(|r| r.repository.as_deref()),
+            Some("full/repo")
+        );
+        assert_eq!(best_reference(&[]), None);
+    }
+
+    #[test]
+    fn test_best_reference_tie_keeps_first() {
+        let refs = vec![
+            Reference::new().with_repository("first"),
+            Reference::new().with_repository("second"),
+        ];
+        assert_eq!(
+            best_reference(&refs).and_then(|r| r.repository.as_deref()),
+            Some("first")
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! 共享的 serde 辅助函数
+//!
+//! 用于容忍后端返回格式不一致的字段，以及序列化相关的统计工具
+
+use std::io;
+
+use serde::de::{self, Deserializer, Unexpected};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use super::enums::UserIntent;
+
+/// 宽松的布尔值原始表示
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FlexibleBool {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+}
+
+/// 宽松反序列化布尔值
+///
+/// 接受 `true` / `false`、`"true"` / `"false"` 以及 `0` / `1`
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[derive(Deserialize)]
+/// struct Flags {
+///     #[serde(deserialize_with = "deserialize_flexible_bool")]
+///     enabled: bool,
+/// }
+/// ```
+#[allow(dead_code)]
+pub fn deserialize_flexible_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match FlexibleBool::deserialize(deserializer)? {
+        FlexibleBool::Bool(value) => Ok(value),
+        FlexibleBool::Int(0) => Ok(false),
+        FlexibleBool::Int(1) => Ok(true),
+        FlexibleBool::Int(other) => Err(de::Error::invalid_value(
+            Unexpected::Signed(other),
+            &"0 或 1",
+        )),
+        FlexibleBool::Str(value) => match value.as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            other => Err(de::Error::invalid_value(
+                Unexpected::Str(other),
+                &"\"true\" 或 \"false\"",
+            )),
+        },
+    }
+}
+
+/// 从旧版客户端的整数编码反序列化用户意图
+///
+/// 编码映射见 [`UserIntent::code`]，未知编码返回错误
+#[allow(dead_code)]
+pub fn deserialize_user_intent_code<'de, D>(deserializer: D) -> Result<UserIntent, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let code = u64::deserialize(deserializer)?;
+    UserIntent::from_code(code).ok_or_else(|| {
+        de::Error::invalid_value(Unexpected::Unsigned(code), &"0 到 9 之间的意图编码")
+    })
+}
+
+/// 将显式 `null` 反序列化为默认值
+///
+/// 配合 `#[serde(default)]` 使用时，字段缺失与 `null` 都得到默认值（如空数组）
+pub fn deserialize_null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Option::unwrap_or_default)
+}
+
+/// 区分字段缺失、显式 `null` 与具体值的可选类型
+///
+/// 需配合 `#[serde(default)]` 使用：字段缺失时为 `Absent`，
+/// 值为 `null` 时为 `Null`。序列化时 `Absent` 与 `Null` 均输出 `null`，
+/// 通常再配合 `skip_serializing_if = "Presence::is_absent"` 省略缺失字段
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Presence<T> {
+    /// 字段缺失
+    #[default]
+    Absent,
+    /// 字段显式为 null
+    Null,
+    /// 字段有值
+    Value(T),
+}
+
+#[allow(dead_code)]
+impl<T> Presence<T> {
+    /// 判断字段是否缺失
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    /// 判断字段是否显式为 null
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// 获取值的引用，缺失或 null 时返回 `None`
+    pub fn as_option(&self) -> Option<&T> {
+        match self {
+            Self::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Presence<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::Value(value),
+            None => Self::Null,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Presence<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Value(value) => value.serialize(serializer),
+            Self::Absent | Self::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Presence<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<T>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// 只统计字节数的写入器
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 评分序列化时保留的小数位数
+pub const SCORE_DECIMALS: i32 = 4;
+
+/// 评分字段的表示形式
+///
+/// 使 [`serialize_score_rounded`] 同时适用于 `Option<f64>` 和 [`Presence<f64>`]
+pub trait ScoreRepr {
+    /// 获取评分值，缺失或为 null 时返回 `None`
+    fn score_value(&self) -> Option<f64>;
+}
+
+impl ScoreRepr for Option<f64> {
+    fn score_value(&self) -> Option<f64> {
+        *self
+    }
+}
+
+impl ScoreRepr for Presence<f64> {
+    fn score_value(&self) -> Option<f64> {
+        self.as_option().copied()
+    }
+}
+
+/// 将评分四舍五入到 [`SCORE_DECIMALS`] 位小数后序列化
+///
+/// 配合 `#[serde(serialize_with = "serialize_score_rounded")]` 使用，使输出稳定可复现；
+/// 没有评分值时序列化为 null（缺失的字段应由 `skip_serializing_if` 跳过）
+pub fn serialize_score_rounded<T, S>(score: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ScoreRepr,
+    S: Serializer,
+{
+    let factor = 10f64.powi(SCORE_DECIMALS);
+    score
+        .score_value()
+        .map(|value| (value * factor).round() / factor)
+        .serialize(serializer)
+}
+
+/// 计算值序列化为紧凑 JSON 后的字节长度
+///
+/// 边序列化边计数，不保留生成的字符串。
+///
+/// 返回 `Result` 而非直接返回长度：`T` 为任意 [`Serialize`] 类型，序列化本身可能失败
+/// （如以非字符串为键的映射、自定义实现返回错误），此时没有合理的长度可以报告，
+/// 返回 0 或 panic 都会掩盖问题
+#[allow(dead_code)]
+pub fn serialized_len<T: Serialize>(value: &T) -> Result<usize, serde_json::Error> {
+    let mut counter = ByteCounter(0);
+    serde_json::to_writer(&mut counter, value)?;
+    Ok(counter.0)
+}
+
+/// 序列化为便于调试的格式化 JSON
+///
+/// 先转换为 `serde_json::Value` 并递归按字母顺序重排对象键，
+/// 即使依赖图中启用了 `serde_json/preserve_order` 输出也稳定可比对；
+/// 序列化失败时返回描述错误的占位字符串
+#[cfg(feature = "pretty-debug")]
+#[allow(dead_code)]
+pub fn to_pretty_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .and_then(|value| serde_json::to_string_pretty(&sort_keys(value)))
+        .unwrap_or_else(|e| format!("<序列化失败: {}>", e))
+}
+
+/// 递归按键排序 JSON 对象
+#[cfg(feature = "pretty-debug")]
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Flags {
+        #[serde(deserialize_with = "deserialize_flexible_bool")]
+        enabled: bool,
+    }
+
+    fn parse(json: &str) -> Result<bool, serde_json::Error> {
+        serde_json::from_str::<Flags>(json).map(|flags| flags.enabled)
+    }
+
+    #[test]
+    fn test_bool() {
+        assert!(parse(r#"{"enabled":true}"#).unwrap());
+        assert!(!parse(r#"{"enabled":false}"#).unwrap());
+    }
+
+    #[test]
+    fn test_string() {
+        assert!(parse(r#"{"enabled":"true"}"#).unwrap());
+        assert!(!parse(r#"{"enabled":"false"}"#).unwrap());
+    }
+
+    #[test]
+    fn test_number() {
+        assert!(parse(r#"{"enabled":1}"#).unwrap());
+        assert!(!parse(r#"{"enabled":0}"#).unwrap());
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(parse(r#"{"enabled":2}"#).is_err());
+        assert!(parse(r#"{"enabled":"yes"}"#).is_err());
+    }
+
+    #[test]
+    fn test_serialized_len() {
+        use crate::kiro::model::common::reference::Reference;
+
+        let minimal = Reference::new();
+        let full = Reference::new()
+            .with_license_name("MIT")
+            .with_repository("owner/repo")
+            .with_url("https://github.com/owner/repo")
+            .with_span_range(0..10);
+
+        assert_eq!(serialized_len(&minimal).unwrap(), 2);
+        assert_eq!(
+            serialized_len(&full).unwrap(),
+            serde_json::to_string(&full).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_serialized_len_error() {
+        // JSON 对象的键必须是字符串，元组键会在序列化中途失败
+        let mut map = std::collections::BTreeMap::new();
+        map.insert((1, 2), "value");
+
+        assert!(serialized_len(&map).is_err());
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct LegacyPrompt {
+        #[serde(deserialize_with = "deserialize_user_intent_code")]
+        intent: UserIntent,
+    }
+
+    #[test]
+    fn test_deserialize_user_intent_code() {
+        let parse = |json| serde_json::from_str::<LegacyPrompt>(json).map(|p| p.intent);
+
+        assert_eq!(
+            parse(r#"{"intent":0}"#).unwrap(),
+            UserIntent::ExplainCodeSelection
+        );
+        assert_eq!(
+            parse(r#"{"intent":9}"#).unwrap(),
+            UserIntent::CodeGeneration
+        );
+        assert!(parse(r#"{"intent":42}"#).is_err());
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Scored {
+        #[serde(default, skip_serializing_if = "Presence::is_absent")]
+        score: Presence<f64>,
+    }
+
+    #[test]
+    fn test_presence_deserialize() {
+        let absent: Scored = serde_json::from_str("{}").unwrap();
+        let null: Scored = serde_json::from_str(r#"{"score":null}"#).unwrap();
+        let value: Scored = serde_json::from_str(r#"{"score":0.5}"#).unwrap();
+
+        assert_eq!(absent.score, Presence::Absent);
+        assert_eq!(null.score, Presence::Null);
+        assert_eq!(value.score, Presence::Value(0.5));
+    }
+
+    #[test]
+    fn test_presence_serialize() {
+        let encode = |score| serde_json::to_string(&Scored { score }).unwrap();
+        assert_eq!(encode(Presence::Absent), "{}");
+        assert_eq!(encode(Presence::Null), r#"{"score":null}"#);
+        assert_eq!(encode(Presence::Value(0.5)), r#"{"score":0.5}"#);
+    }
+
+    #[cfg(feature = "pretty-debug")]
+    #[test]
+    fn test_to_pretty_json() {
+        use crate::kiro::model::common::link::SupplementaryWebLink;
+
+        let link = SupplementaryWebLink::new("https://example.com").with_title("Example");
+        let pretty = to_pretty_json(&link);
+
+        assert!(pretty.lines().count() > 1);
+        // 键按字母顺序输出，而不是字段声明顺序
+        assert!(pretty.find("title").unwrap() < pretty.find("url").unwrap());
+        let parsed: SupplementaryWebLink = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(parsed, link);
+    }
+
+    #[cfg(feature = "pretty-debug")]
+    #[test]
+    fn test_to_pretty_json_sorts_nested_keys() {
+        let value = serde_json::json!({"z": [{"b": 1, "a": 2}], "m": {"y": 1, "x": 2}});
+        let compact: String = to_pretty_json(&value)
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+
+        assert_eq!(compact, r#"{"m":{"x":2,"y":1},"z":[{"a":2,"b":1}]}"#);
+    }
+
+    #[test]
+    fn test_serialize_score_rounded() {
+        #[derive(Serialize)]
+        struct Scored {
+            #[serde(serialize_with = "serialize_score_rounded")]
+            score: Option<f64>,
+        }
+
+        let json = serde_json::to_string(&Scored {
+            score: Some(0.123456789),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"score":0.1235}"#);
+
+        let json = serde_json::to_string(&Scored { score: None }).unwrap();
+        assert_eq!(json, r#"{"score":null}"#);
+    }
+
+    #[test]
+    fn test_serialize_score_rounded_presence() {
+        #[derive(Serialize)]
+        struct Scored {
+            #[serde(serialize_with = "serialize_score_rounded")]
+            score: Presence<f64>,
+        }
+
+        let json = serde_json::to_string(&Scored {
+            score: Presence::Value(0.123456789),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"score":0.1235}"#);
+
+        let json = serde_json::to_string(&Scored {
+            score: Presence::Null,
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"score":null}"#);
+    }
+}
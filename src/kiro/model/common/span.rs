@@ -0,0 +1,475 @@
+//! 内容范围定义
+//!
+//! 定义引用在响应文本中标记的内容范围
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::ModelError;
+
+/// 内容长度上限（10 MB）
+///
+/// 超过此长度的范围视为异常数据
+#[allow(dead_code)]
+pub const MAX_CONTENT_LENGTH: i32 = 10 * 1024 * 1024;
+
+/// 内容范围
+///
+/// 以 `[start, end)` 半开区间标记响应内容中的一段文本
+///
+/// 反序列化同时接受 `{"start", "end"}` 与 `{"start", "length"}` 两种形式，
+/// 序列化始终输出 `start` / `end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", try_from = "RawContentSpan")]
+pub struct ContentSpan {
+    /// 起始位置（包含）
+    pub start: i32,
+    /// 结束位置（不包含）
+    pub end: i32,
+}
+
+/// 内容范围的原始线上表示
+///
+/// 部分后端会在范围对象中附带 `kind` 等额外字段，这里不拒绝未知字段
+#[derive(Deserialize)]
+struct RawContentSpan {
+    start: i32,
+    end: Option<i32>,
+    length: Option<i32>,
+}
+
+impl TryFrom<RawContentSpan> for ContentSpan {
+    type Error = ModelError;
+
+    fn try_from(raw: RawContentSpan) -> Result<Self, Self::Error> {
+        match (raw.end, raw.length) {
+            (Some(end), _) => Ok(Self::new(raw.start, end)),
+            (None, Some(length)) => Self::try_from_start_len(raw.start, length),
+            (None, None) => Err(ModelError::MissingSpanEnd),
+        }
+    }
+}
+
+impl ContentSpan {
+    /// 创建新的内容范围
+    pub fn new(start: i32, end: i32) -> Self {
+        Self { start, end }
+    }
+
+    /// 以起始位置和长度创建范围
+    ///
+    /// 结束位置溢出时饱和到 `i32::MAX`；需要校验时使用 [`Self::try_from_start_len`]
+    pub fn from_start_len(start: i32, len: i32) -> Self {
+        Self::new(start, start.saturating_add(len))
+    }
+
+    /// 以起始位置和长度创建范围（带校验）
+    ///
+    /// 长度为负或结束位置溢出时返回错误
+    pub fn try_from_start_len(start: i32, len: i32) -> Result<Self, ModelError> {
+        if len < 0 {
+            return Err(ModelError::NegativeSpanLength(len));
+        }
+        let end = start
+            .checked_add(len)
+            .ok_or(ModelError::SpanOverflow { start, len })?;
+        Ok(Self::new(start, end))
+    }
+
+    /// 创建不超过上限的范围（带校验）
+    ///
+    /// 范围为负、倒置或结束位置超过 `limit` 时返回错误
+    pub fn try_new_bounded(start: i32, end: i32, limit: i32) -> Result<Self, ModelError> {
+        let span = Self::new(start, end);
+        if !span.within_limit(limit) {
+            return Err(ModelError::SpanOutOfBounds { start, end, limit });
+        }
+        Ok(span)
+    }
+
+    /// 判断范围是否有效且结束位置不超过 `limit`
+    ///
+    /// 通常以 [`MAX_CONTENT_LENGTH`] 作为上限
+    pub fn within_limit(&self, limit: i32) -> bool {
+        self.start >= 0 && self.start <= self.end && self.end <= limit
+    }
+
+    /// 获取范围长度（倒置范围视为 0，超出 `i32` 时饱和为 `i32::MAX`）
+    pub fn len(&self) -> i32 {
+        self.end.saturating_sub(self.start).max(0)
+    }
+
+    /// 判断范围是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 判断是否为有效的零长度范围（插入点）
+    pub fn is_point(&self) -> bool {
+        self.start == self.end && self.start >= 0
+    }
+
+    /// 判断位置是否落在范围内（`start <= offset < end`）
+    pub fn contains(&self, offset: i32) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    /// 遍历范围覆盖的所有位置
+    ///
+    /// 空范围或倒置范围不产生任何元素
+    pub fn iter(&self) -> impl Iterator<Item = i32> {
+        self.start..self.end
+    }
+
+    /// 截取范围对应的文本（按字节偏移）
+    ///
+    /// 范围为负、倒置、越界或不在字符边界上时返回 `None`
+    pub fn slice(self, text: &str) -> Option<&str> {
+        if self.start < 0 || self.end < self.start {
+            return None;
+        }
+        text.get(self.start as usize..self.end as usize)
+    }
+
+    /// 将字节偏移范围转换为 UTF-16 码元偏移范围
+    ///
+    /// 供 VS Code 等使用 UTF-16 偏移的编辑器使用；范围无效时返回 `None`
+    pub fn to_utf16(self, text: &str) -> Option<ContentSpan> {
+        let cited = self.slice(text)?;
+        let start = text[..self.start as usize].encode_utf16().count();
+        let end = start + cited.encode_utf16().count();
+        Some(ContentSpan::new(
+            i32::try_from(start).ok()?,
+            i32::try_from(end).ok()?,
+        ))
+    }
+
+    /// 根据一次文本编辑调整范围
+    ///
+    /// `edit` 为被替换的原始范围，`delta` 为替换后长度的变化量。
+    /// 编辑点之前的位置不变，编辑区域之后的位置整体平移 `delta`，
+    /// 落在被替换区域内部的位置收缩到替换文本的末尾以内。
+    /// 平移结果超出 `i32` 范围时饱和到边界值
+    pub fn shift_for_edit(self, edit: ContentSpan, delta: i32) -> Self {
+        let new_end = edit.end.saturating_add(delta);
+        let map = |pos: i32| {
+            if pos <= edit.start {
+                pos
+            } else if pos >= edit.end {
+                pos.saturating_add(delta)
+            } else {
+                pos.min(new_end)
+            }
+        };
+        Self::new(map(self.start), map(self.end))
+    }
+
+    /// 将字节偏移范围转换为 `(行, 列)` 起止位置
+    ///
+    /// 行号从 1 开始，列号为该行内的字节偏移（从 0 开始）。
+    /// 范围为负、倒置或超出文本长度时返回 `None`
+    pub fn to_line_col(self, text: &str) -> Option<((u32, u32), (u32, u32))> {
+        if self.start < 0 || self.end < self.start || self.end as usize > text.len() {
+            return None;
+        }
+        Some((
+            line_col_at(text, self.start as usize),
+            line_col_at(text, self.end as usize),
+        ))
+    }
+
+    /// 判断范围是否完全位于 `container` 之内（边界相等也算在内）
+    pub fn is_within(&self, container: &ContentSpan) -> bool {
+        container.start <= self.start && self.end <= container.end
+    }
+
+    /// 计算与另一范围的重叠比例（交集长度除以并集长度）
+    ///
+    /// 相同范围返回 1.0，不相交返回 0.0；两个范围均为空时，相等返回 1.0，否则返回 0.0
+    pub fn overlap_ratio(&self, other: &ContentSpan) -> f64 {
+        let intersection =
+            (i64::from(self.end.min(other.end)) - i64::from(self.start.max(other.start))).max(0);
+        let union = i64::from(self.len()) + i64::from(other.len()) - intersection;
+        if union <= 0 {
+            return if self == other { 1.0 } else { 0.0 };
+        }
+        intersection as f64 / union as f64
+    }
+
+    /// 以自定义字段名序列化为 JSON 对象
+    ///
+    /// 用于对接使用其他字段名（如 `begin` / `finish`）的系统
+    pub fn serialize_as(&self, start_key: &str, end_key: &str) -> serde_json::Value {
+        let mut object = serde_json::Map::new();
+        object.insert(start_key.to_string(), self.start.into());
+        object.insert(end_key.to_string(), self.end.into());
+        serde_json::Value::Object(object)
+    }
+}
+
+impl From<Range<i32>> for ContentSpan {
+    fn from(range: Range<i32>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+/// 计算字节偏移所在的行列
+fn line_col_at(text: &str, offset: usize) -> (u32, u32) {
+    let before = &text.as_bytes()[..offset];
+    let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+    let line_start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |pos| pos + 1);
+    (line as u32, (offset - line_start) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_len() {
+        assert_eq!(ContentSpan::new(2, 5).len(), 3);
+        assert_eq!(ContentSpan::new(5, 2).len(), 0);
+        assert!(ContentSpan::new(5, 5).is_empty());
+    }
+
+    #[test]
+    fn test_len_does_not_overflow() {
+        assert_eq!(ContentSpan::new(i32::MIN, i32::MAX).len(), i32::MAX);
+        assert_eq!(ContentSpan::new(i32::MAX, i32::MIN).len(), 0);
+    }
+
+    #[test]
+    fn test_from_start_len() {
+        assert_eq!(ContentSpan::from_start_len(10, 5), ContentSpan::new(10, 15));
+    }
+
+    #[test]
+    fn test_try_from_start_len() {
+        assert_eq!(
+            ContentSpan::try_from_start_len(10, 5),
+            Ok(ContentSpan::new(10, 15))
+        );
+        assert_eq!(
+            ContentSpan::try_from_start_len(10, -1),
+            Err(ModelError::NegativeSpanLength(-1))
+        );
+        assert_eq!(
+            ContentSpan::try_from_start_len(i32::MAX, 1),
+            Err(ModelError::SpanOverflow {
+                start: i32::MAX,
+                len: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_is_point() {
+        assert!(ContentSpan::new(5, 5).is_point());
+        assert!(!ContentSpan::new(5, 6).is_point());
+        assert!(!ContentSpan::new(-1, -1).is_point());
+    }
+
+    #[test]
+    fn test_contains() {
+        let span = ContentSpan::new(2, 5);
+        assert!(span.contains(2));
+        assert!(span.contains(4));
+        assert!(!span.contains(5));
+        assert!(!span.contains(1));
+    }
+
+    #[test]
+    fn test_within_limit() {
+        assert!(ContentSpan::new(0, 100).within_limit(MAX_CONTENT_LENGTH));
+        assert!(ContentSpan::new(0, 10).within_limit(10));
+        assert!(!ContentSpan::new(0, MAX_CONTENT_LENGTH + 1).within_limit(MAX_CONTENT_LENGTH));
+        assert!(!ContentSpan::new(-1, 5).within_limit(10));
+    }
+
+    #[test]
+    fn test_try_new_bounded() {
+        assert_eq!(
+            ContentSpan::try_new_bounded(1, 5, 10),
+            Ok(ContentSpan::new(1, 5))
+        );
+        assert_eq!(
+            ContentSpan::try_new_bounded(1, 50, 10),
+            Err(ModelError::SpanOutOfBounds {
+                start: 1,
+                end: 50,
+                limit: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_iter() {
+        assert_eq!(
+            ContentSpan::new(2, 5).iter().collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(ContentSpan::new(5, 5).iter().count(), 0);
+        assert_eq!(ContentSpan::new(5, 2).iter().count(), 0);
+    }
+
+    #[test]
+    fn test_from_range() {
+        assert_eq!(ContentSpan::from(3..7), ContentSpan::new(3, 7));
+    }
+
+    #[test]
+    fn test_deserialize_start_end() {
+        let span: ContentSpan = serde_json::from_str(r#"{"start":10,"end":15}"#).unwrap();
+        assert_eq!(span, ContentSpan::new(10, 15));
+    }
+
+    #[test]
+    fn test_deserialize_start_length() {
+        let span: ContentSpan = serde_json::from_str(r#"{"start":10,"length":5}"#).unwrap();
+        assert_eq!(span, ContentSpan::new(10, 15));
+        // 序列化仍输出 start / end
+        assert_eq!(
+            serde_json::to_string(&span).unwrap(),
+            r#"{"start":10,"end":15}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        assert!(serde_json::from_str::<ContentSpan>(r#"{"start":10}"#).is_err());
+        assert!(serde_json::from_str::<ContentSpan>(r#"{"start":10,"length":-1}"#).is_err());
+    }
+
+    #[test]
+    fn test_serialize() {
+        let json = serde_json::to_string(&ContentSpan::new(1, 2)).unwrap();
+        assert_eq!(json, r#"{"start":1,"end":2}"#);
+    }
+
+    #[test]
+    fn test_slice() {
+        assert_eq!(ContentSpan::new(1, 3).slice("abcd"), Some("bc"));
+        assert_eq!(ContentSpan::new(3, 1).slice("abcd"), None);
+        assert_eq!(ContentSpan::new(0, 9).slice("abcd"), None);
+        // 不在字符边界上
+        assert_eq!(ContentSpan::new(0, 1).slice("中文"), None);
+    }
+
+    #[test]
+    fn test_shift_for_edit() {
+        let edit = ContentSpan::new(5, 8);
+        // 编辑之前的范围不变
+        assert_eq!(
+            ContentSpan::new(0, 5).shift_for_edit(edit, 2),
+            ContentSpan::new(0, 5)
+        );
+        // 编辑之后的范围整体平移
+        assert_eq!(
+            ContentSpan::new(8, 10).shift_for_edit(edit, 2),
+            ContentSpan::new(10, 12)
+        );
+        // 包含编辑区域的范围只调整结束位置
+        assert_eq!(
+            ContentSpan::new(2, 12).shift_for_edit(edit, -2),
+            ContentSpan::new(2, 10)
+        );
+    }
+
+    #[test]
+    fn test_shift_for_edit_saturates() {
+        let edit = ContentSpan::new(5, 8);
+        assert_eq!(
+            ContentSpan::new(10, i32::MAX - 1).shift_for_edit(edit, 10),
+            ContentSpan::new(20, i32::MAX)
+        );
+        assert_eq!(
+            ContentSpan::new(6, 10).shift_for_edit(edit, i32::MAX),
+            ContentSpan::new(6, i32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_to_utf16() {
+        // "😀" 占 4 字节、2 个 UTF-16 码元
+        let text = "a😀b";
+        let span = ContentSpan::new(5, 6);
+        assert_eq!(span.slice(text), Some("b"));
+        assert_eq!(span.to_utf16(text), Some(ContentSpan::new(3, 4)));
+        assert_eq!(
+            ContentSpan::new(1, 5).to_utf16(text),
+            Some(ContentSpan::new(1, 3))
+        );
+        // 不在字符边界上
+        assert_eq!(ContentSpan::new(2, 5).to_utf16(text), None);
+    }
+
+    #[test]
+    fn test_to_line_col() {
+        let text = "fn main() {\n    foo();\n}\n";
+        // "foo" 位于第 2 行第 4 列
+        assert_eq!(
+            ContentSpan::new(16, 19).to_line_col(text),
+            Some(((2, 4), (2, 7)))
+        );
+        // 跨越多行
+        assert_eq!(
+            ContentSpan::new(0, 24).to_line_col(text),
+            Some(((1, 0), (3, 1)))
+        );
+    }
+
+    #[test]
+    fn test_to_line_col_ending_at_newline() {
+        let text = "abc\ndef";
+        // 结束位置正好是换行符，仍属于第 1 行
+        assert_eq!(
+            ContentSpan::new(0, 3).to_line_col(text),
+            Some(((1, 0), (1, 3)))
+        );
+        // 结束位置在换行符之后，进入第 2 行
+        assert_eq!(
+            ContentSpan::new(0, 4).to_line_col(text),
+            Some(((1, 0), (2, 0)))
+        );
+    }
+
+    #[test]
+    fn test_to_line_col_out_of_bounds() {
+        assert_eq!(ContentSpan::new(0, 10).to_line_col("abc"), None);
+        assert_eq!(ContentSpan::new(-1, 2).to_line_col("abc"), None);
+    }
+
+    #[test]
+    fn test_serialize_as() {
+        assert_eq!(
+            ContentSpan::new(1, 2).serialize_as("begin", "finish"),
+            serde_json::json!({"begin": 1, "finish": 2})
+        );
+    }
+
+    #[test]
+    fn test_overlap_ratio() {
+        let span = ContentSpan::new(0, 10);
+
+        assert_eq!(span.overlap_ratio(&ContentSpan::new(0, 10)), 1.0);
+        assert_eq!(span.overlap_ratio(&ContentSpan::new(10, 20)), 0.0);
+        assert_eq!(span.overlap_ratio(&ContentSpan::new(5, 15)), 5.0 / 15.0);
+        assert_eq!(
+            ContentSpan::new(3, 3).overlap_ratio(&ContentSpan::new(3, 3)),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_is_within() {
+        let container = ContentSpan::new(10, 20);
+
+        assert!(ContentSpan::new(12, 18).is_within(&container));
+        assert!(!ContentSpan::new(15, 25).is_within(&container));
+        assert!(container.is_within(&container));
+    }
+}
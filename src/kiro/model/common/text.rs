@@ -0,0 +1,394 @@
+//! 引用文本编辑辅助
+//!
+//! 在修改响应文本的同时保持引用范围与文本的对应关系
+
+use super::coverage::merged_spans;
+use super::reference::Reference;
+use super::span::ContentSpan;
+
+/// 替换文本中的一段内容，并同步调整引用范围
+///
+/// `span` 使用字节偏移；范围无效（为负、倒置、越界或不在字符边界上）时不做任何修改。
+/// 编辑点之后的引用范围按长度变化量平移
+#[allow(dead_code)]
+pub fn apply_replacement(
+    text: &mut String,
+    span: ContentSpan,
+    replacement: &str,
+    refs: &mut [Reference],
+) {
+    if span.slice(text).is_none() {
+        return;
+    }
+    text.replace_range(span.start as usize..span.end as usize, replacement);
+
+    let delta = i32::try_from(replacement.len())
+        .unwrap_or(i32::MAX)
+        .saturating_sub(span.len());
+    remap_spans_through_edits(refs, &[(span, delta)]);
+}
+
+/// 按编辑序列依次调整引用范围
+///
+/// 每个编辑为 `(被替换的范围, 长度变化量)`，范围基于前一个编辑完成后的文本；
+/// 调整规则见 [`ContentSpan::shift_for_edit`]
+pub fn remap_spans_through_edits(refs: &mut [Reference], edits: &[(ContentSpan, i32)]) {
+    for reference in refs.iter_mut() {
+        if let Some(span) = &mut reference.recommendation_content_span {
+            for &(edit, delta) in edits {
+                *span = span.shift_for_edit(edit, delta);
+            }
+        }
+    }
+}
+
+/// 将范围从原始文本坐标映射到空白规范化后的文本坐标
+///
+/// 通过逐字符对齐两段文本建立偏移映射表：原始文本中被折叠或删除的空白
+/// 映射到规范化文本中的当前位置。两段文本除空白外不一致、或范围不在字符边界上时返回 `None`
+#[allow(dead_code)]
+pub fn remap_span_after_normalization(
+    original: &str,
+    normalized: &str,
+    span: ContentSpan,
+) -> Option<ContentSpan> {
+    let table = whitespace_alignment(original, normalized)?;
+    let map = |pos: i32| -> Option<i32> {
+        let pos = usize::try_from(pos).ok()?;
+        table
+            .get(pos)
+            .copied()
+            .flatten()
+            .map(|mapped| mapped as i32)
+    };
+    Some(ContentSpan::new(map(span.start)?, map(span.end)?))
+}
+
+/// 构建原始文本字节偏移到规范化文本字节偏移的对齐表
+///
+/// 表长度为 `original.len() + 1`，非字符边界位置为 `None`
+fn whitespace_alignment(original: &str, normalized: &str) -> Option<Vec<Option<usize>>> {
+    let mut table = vec![None; original.len() + 1];
+    let mut target = normalized.char_indices().peekable();
+
+    for (offset, ch) in original.char_indices() {
+        // 跳过规范化文本中插入的空白
+        while let Some(&(_, t)) = target.peek() {
+            if t != ch && t.is_whitespace() {
+                target.next();
+            } else {
+                break;
+            }
+        }
+        let position = target.peek().map_or(normalized.len(), |&(pos, _)| pos);
+        table[offset] = Some(position);
+        match target.peek() {
+            Some(&(_, t)) if t == ch => {
+                target.next();
+            }
+            _ if ch.is_whitespace() => {}
+            _ => return None,
+        }
+    }
+    table[original.len()] = Some(normalized.len());
+    Some(table)
+}
+
+/// 生成保留引用区域的文本预览
+///
+/// 每个引用范围前后各保留 `context` 字节（向外对齐到字符边界），
+/// 相互重叠的窗口合并，其余部分折叠为 `…`；没有有效引用范围时整段折叠
+#[allow(dead_code)]
+pub fn preview_with_citations(text: &str, refs: &[Reference], context: usize) -> String {
+    let len = text.len();
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for span in merged_spans(refs) {
+        let start = span.start.max(0) as usize;
+        let end = (span.end.max(0) as usize).min(len);
+        if start >= end {
+            continue;
+        }
+        let mut window_start = start.saturating_sub(context);
+        while !text.is_char_boundary(window_start) {
+            window_start -= 1;
+        }
+        let mut window_end = end.saturating_add(context).min(len);
+        while !text.is_char_boundary(window_end) {
+            window_end += 1;
+        }
+        match windows.last_mut() {
+            Some(last) if window_start <= last.1 => last.1 = last.1.max(window_end),
+            _ => windows.push((window_start, window_end)),
+        }
+    }
+
+    if windows.is_empty() {
+        return if text.is_empty() {
+            String::new()
+        } else {
+            "…".to_string()
+        };
+    }
+
+    let mut preview = String::new();
+    let mut cursor = 0;
+    for (start, end) in windows {
+        if start > cursor {
+            preview.push('…');
+        }
+        preview.push_str(&text[start..end]);
+        cursor = end;
+    }
+    if cursor < len {
+        preview.push('…');
+    }
+    preview
+}
+
+/// 按文档顺序列出引用及其引用的文本
+///
+/// 按内容范围起始位置排序（相同时按结束位置），跳过未设置范围或范围无效的引用
+#[allow(dead_code)]
+pub fn cited_texts<'a>(refs: &'a [Reference], text: &'a str) -> Vec<(&'a Reference, &'a str)> {
+    let mut cited: Vec<(ContentSpan, &Reference, &str)> = refs
+        .iter()
+        .filter_map(|reference| {
+            let span = reference.recommendation_content_span?;
+            Some((span, reference, span.slice(text)?))
+        })
+        .collect();
+    cited.sort_by_key(|(span, _, _)| (span.start, span.end));
+    cited
+        .into_iter()
+        .map(|(_, reference, slice)| (reference, slice))
+        .collect()
+}
+
+/// 在每个引用范围的末尾插入编号标记
+///
+/// 标记为 `[n]`，`n` 是引用在 `refs` 中的序号（从 1 开始），便于与引用列表对应；
+/// 多个引用在同一位置结束时按序号升序排列。范围无效的引用不插入标记，但仍占用序号
+#[allow(dead_code)]
+pub fn annotate_citations(text: &str, refs: &[Reference]) -> String {
+    let mut markers: Vec<(usize, usize)> = refs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, reference)| {
+            let span = reference.recommendation_content_span?;
+            span.slice(text)?;
+            Some((span.end as usize, index + 1))
+        })
+        .collect();
+    markers.sort_unstable();
+
+    let mut annotated = String::with_capacity(text.len() + markers.len() * 4);
+    let mut cursor = 0;
+    for (offset, number) in markers {
+        annotated.push_str(&text[cursor..offset]);
+        annotated.push_str(&format!("[{}]", number));
+        cursor = offset;
+    }
+    annotated.push_str(&text[cursor..]);
+    annotated
+}
+
+/// 转义引用区域中的 Markdown 反引号
+///
+/// 对 `span` 截取的文本中的反引号和反斜杠加反斜杠转义，返回转义后的文本
+/// 以及起点不变、长度按转义结果调整后的范围，便于继续定位引用标记；
+/// 范围无效时返回空字符串和位于起点的空范围
+#[allow(dead_code)]
+pub fn escape_cited_region(text: &str, span: ContentSpan) -> (String, ContentSpan) {
+    let Some(region) = span.slice(text) else {
+        return (String::new(), ContentSpan::new(span.start, span.start));
+    };
+    let mut escaped = String::with_capacity(region.len());
+    for c in region.chars() {
+        if matches!(c, '`' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    let adjusted = ContentSpan::from_start_len(span.start, escaped.len() as i32);
+    (escaped, adjusted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_replacement() {
+        let mut text = String::from("let secret = 1; let x = secret;");
+        let mut refs = vec![
+            Reference::new().with_url("https://a").with_span_range(0..3),
+            Reference::new()
+                .with_url("https://b")
+                .with_span_range(16..31),
+        ];
+
+        apply_replacement(&mut text, ContentSpan::new(4, 10), "s", &mut refs);
+
+        assert_eq!(text, "let s = 1; let x = secret;");
+        assert_eq!(
+            refs[0].recommendation_content_span,
+            Some(ContentSpan::new(0, 3))
+        );
+        assert_eq!(
+            refs[1].recommendation_content_span,
+            Some(ContentSpan::new(11, 26))
+        );
+        assert_eq!(
+            refs[1].recommendation_content_span.unwrap().slice(&text),
+            Some("let x = secret;")
+        );
+    }
+
+    #[test]
+    fn test_apply_replacement_invalid_span() {
+        let mut text = String::from("abc");
+        apply_replacement(&mut text, ContentSpan::new(2, 10), "x", &mut []);
+        assert_eq!(text, "abc");
+    }
+
+    #[test]
+    fn test_remap_span_after_normalization() {
+        let original = "let  x  =  compute();";
+        let normalized = "let x = compute();";
+        // 原文中 "compute()" 的范围
+        let span = ContentSpan::new(11, 20);
+        assert_eq!(span.slice(original), Some("compute()"));
+
+        let remapped = remap_span_after_normalization(original, normalized, span).unwrap();
+        assert_eq!(remapped.slice(normalized), Some("compute()"));
+    }
+
+    #[test]
+    fn test_remap_span_after_normalization_trimmed() {
+        let original = "  hello   world  ";
+        let normalized = "hello world";
+        let span = ContentSpan::new(2, 15);
+
+        let remapped = remap_span_after_normalization(original, normalized, span).unwrap();
+        assert_eq!(remapped, ContentSpan::new(0, 11));
+    }
+
+    #[test]
+    fn test_remap_span_after_normalization_mismatch() {
+        let span = ContentSpan::new(0, 1);
+        assert_eq!(remap_span_after_normalization("abc", "xyz", span), None);
+    }
+
+    #[test]
+    fn test_preview_with_citations() {
+        let text = "0123456789 first cited 0123456789 second cited 0123456789";
+        let refs = vec![
+            Reference::new()
+                .with_url("https://a")
+                .with_span_range(11..22),
+            Reference::new()
+                .with_url("https://b")
+                .with_span_range(34..46),
+        ];
+
+        assert_eq!(
+            preview_with_citations(text, &refs, 1),
+            "… first cited … second cited …"
+        );
+        assert_eq!(preview_with_citations(text, &refs, 100), text);
+    }
+
+    #[test]
+    fn test_preview_with_citations_without_spans() {
+        assert_eq!(preview_with_citations("abc", &[], 2), "…");
+        assert_eq!(preview_with_citations("", &[], 2), "");
+    }
+
+    #[test]
+    fn test_cited_texts() {
+        let text = "fn main() { helper(); }";
+        let refs = vec![
+            Reference::new()
+                .with_repository("b")
+                .with_span_range(12..18),
+            Reference::new().with_repository("none"),
+            Reference::new().with_repository("a").with_span_range(3..7),
+            Reference::new()
+                .with_repository("bad")
+                .with_span_range(20..99),
+        ];
+        let cited = cited_texts(&refs, text);
+
+        assert_eq!(cited.len(), 2);
+        assert_eq!(cited[0].0.repository.as_deref(), Some("a"));
+        assert_eq!(cited[0].1, "main");
+        assert_eq!(cited[1].0.repository.as_deref(), Some("b"));
+        assert_eq!(cited[1].1, "helper");
+    }
+
+    #[test]
+    fn test_annotate_citations() {
+        let text = "first part. second part.";
+        let refs = vec![
+            Reference::new().with_span_range(12..24),
+            Reference::new().with_span_range(0..11),
+        ];
+
+        assert_eq!(
+            annotate_citations(text, &refs),
+            "first part.[2] second part.[1]"
+        );
+    }
+
+    #[test]
+    fn test_annotate_citations_overlapping() {
+        let text = "abcdef";
+        let refs = vec![
+            Reference::new().with_span_range(2..4),
+            Reference::new().with_span_range(0..4),
+            Reference::new().with_span_range(0..99),
+        ];
+
+        assert_eq!(annotate_citations(text, &refs), "abcd[1][2]ef");
+    }
+
+    #[test]
+    fn test_escape_cited_region() {
+        let text = "call `run()` now";
+        let (escaped, span) = escape_cited_region(text, ContentSpan::new(5, 12));
+
+        assert_eq!(escaped, "\\`run()\\`");
+        assert_eq!(span, ContentSpan::new(5, 14));
+    }
+
+    #[test]
+    fn test_escape_cited_region_invalid_span() {
+        let (escaped, span) = escape_cited_region("abc", ContentSpan::new(1, 10));
+
+        assert!(escaped.is_empty());
+        assert_eq!(span, ContentSpan::new(1, 1));
+    }
+
+    #[test]
+    fn test_remap_spans_through_edits() {
+        let mut refs = vec![
+            Reference::new().with_span_range(0..4),
+            Reference::new().with_span_range(30..40),
+        ];
+        // 先替换 10..12 使文本增长 3 字节，再删除 20..25 的 5 字节
+        let edits = [
+            (ContentSpan::new(10, 12), 3),
+            (ContentSpan::new(20, 25), -5),
+        ];
+        remap_spans_through_edits(&mut refs, &edits);
+
+        assert_eq!(
+            refs[0].recommendation_content_span,
+            Some(ContentSpan::new(0, 4))
+        );
+        assert_eq!(
+            refs[1].recommendation_content_span,
+            Some(ContentSpan::new(28, 38))
+        );
+    }
+}
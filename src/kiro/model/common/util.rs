@@ -0,0 +1,73 @@
+//! 共享类型内部辅助函数
+
+/// 去除字符串首尾空白（原地修改）
+pub(crate) fn trim_in_place(value: &mut String) {
+    let trimmed = value.trim();
+    if trimmed.len() != value.len() {
+        *value = trimmed.to_string();
+    }
+}
+
+/// 去除可选字符串首尾空白，去除后为空则置为 `None`
+pub(crate) fn trim_optional(value: &mut Option<String>) {
+    if let Some(inner) = value {
+        trim_in_place(inner);
+        if inner.is_empty() {
+            *value = None;
+        }
+    }
+}
+
+/// 规范化 URL：主机名小写并去除路径末尾的斜杠
+///
+/// 无法解析的 URL 仅去除首尾空白和末尾斜杠
+pub(crate) fn canonicalize_url(raw: &str) -> String {
+    let raw = raw.trim();
+    let Ok(mut url) = url::Url::parse(raw) else {
+        return raw.trim_end_matches('/').to_string();
+    };
+    if let Some(host) = url.host_str().map(str::to_lowercase) {
+        let _ = url.set_host(Some(&host));
+    }
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(&path);
+
+    let mut canonical = url.to_string();
+    if url.path() == "/" && url.query().is_none() && url.fragment().is_none() {
+        canonical.pop();
+    }
+    canonical
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim_optional() {
+        let mut value = Some("  a ".to_string());
+        trim_optional(&mut value);
+        assert_eq!(value.as_deref(), Some("a"));
+
+        let mut value = Some("   ".to_string());
+        trim_optional(&mut value);
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_canonicalize_url() {
+        assert_eq!(
+            canonicalize_url("https://GitHub.com/Owner/Repo/"),
+            "https://github.com/Owner/Repo"
+        );
+        assert_eq!(
+            canonicalize_url("https://Example.com/"),
+            "https://example.com"
+        );
+        assert_eq!(
+            canonicalize_url("https://a.com/x/?q=1"),
+            "https://a.com/x?q=1"
+        );
+        assert_eq!(canonicalize_url(" not a url/ "), "not a url");
+    }
+}
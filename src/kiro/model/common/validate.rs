@@ -0,0 +1,52 @@
+//! 共享类型校验
+//!
+//! 定义统一的校验 trait，并提供先校验再序列化的辅助函数
+
+use serde::Serialize;
+
+use super::error::ModelError;
+
+/// 可校验的类型
+pub trait Validate {
+    /// 校验实例，无效时返回具体错误
+    fn validate(&self) -> Result<(), ModelError>;
+}
+
+/// 校验后序列化为 JSON 字符串
+///
+/// 校验失败时直接返回错误，不会产生任何 JSON
+pub fn serialize_validated<T: Validate + Serialize>(value: &T) -> Result<String, ModelError> {
+    value.validate()?;
+    serde_json::to_string(value).map_err(|e| ModelError::Serialize(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kiro::model::common::link::SupplementaryWebLink;
+    use crate::kiro::model::common::query::CodeQuery;
+    use crate::kiro::model::common::reference::Reference;
+
+    #[test]
+    fn test_serialize_validated() {
+        let reference = Reference::new().with_url("https://a.example");
+        assert_eq!(
+            serialize_validated(&reference).unwrap(),
+            r#"{"url":"https://a.example"}"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_validated_rejects_invalid() {
+        let reference = Reference::new().with_span_range(0..3);
+        assert_eq!(
+            serialize_validated(&reference),
+            Err(ModelError::SpanWithoutSource)
+        );
+        assert_eq!(
+            serialize_validated(&CodeQuery::new("")),
+            Err(ModelError::EmptyField("codeQueryId"))
+        );
+        assert!(serialize_validated(&SupplementaryWebLink::new("bad")).is_err());
+    }
+}